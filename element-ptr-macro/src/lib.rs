@@ -1,54 +1,320 @@
 extern crate proc_macro;
 
-use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro2::{Ident, Spacing, Span, TokenStream, TokenTree};
 
 use proc_macro_crate::FoundCrate;
-use quote::{quote, ToTokens};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::{
-    bracketed, parenthesized,
+    braced, bracketed, parenthesized,
     parse::{Parse, ParseStream},
-    parse_macro_input, token, Expr, Index, LitInt, Token, Type,
+    parse_macro_input,
+    spanned::Spanned,
+    token, Expr, Index, Lifetime, LitInt, RangeLimits, Token, Type,
 };
 
 mod quote_into_hack;
-use quote_into_hack::quote_into;
+use quote_into_hack::{quote_into, quote_spanned_into};
 
 #[proc_macro]
 pub fn element_ptr(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as MacroInput);
 
-    let base_crate = {
-        let found = proc_macro_crate::crate_name("element-ptr").unwrap_or(FoundCrate::Itself);
+    expand_element_ptr(input, &base_crate()).into()
+}
+
+#[proc_macro]
+pub fn element_ptr_bytes(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as MacroInput);
+
+    if let Some(tilde) = input.keep_pointer {
+        return syn::Error::new(
+            tilde.span,
+            "`element_ptr_bytes!` always returns a raw pointer",
+        )
+        .into_compile_error()
+        .into();
+    }
+    if let Some(BaseKind::Pin(kw)) = input.base_kind {
+        return syn::Error::new(kw.span, "`element_ptr_bytes!` cannot keep a pointer pinned")
+            .into_compile_error()
+            .into();
+    }
+    if let Some(span) = input.body.null_check() {
+        return syn::Error::new(span, "`element_ptr_bytes!` cannot check for null pointers")
+            .into_compile_error()
+            .into();
+    }
+
+    let base_crate = base_crate();
+
+    let ptr = expand_element_ptr(input, &base_crate);
+
+    (quote! {
+        :: #base_crate ::helper::new_pointer(#ptr).cast::<u8>().into_inner()
+    })
+    .into()
+}
+
+fn expand_element_ptr(input: MacroInput, base_crate: &Ident) -> TokenStream {
+    let ctx = AccessListToTokensCtx {
+        list: &input.body,
+        base_crate,
+    };
+
+    let ptr = input.ptr;
+
+    let base = record_base();
 
-        match found {
-            FoundCrate::Itself => String::from("element_ptr"),
-            FoundCrate::Name(name) => name,
+    let ptr = match input.base_kind {
+        // point errors about the type of the base at the keyword.
+        Some(BaseKind::Uninit(kw)) => {
+            quote_spanned!(kw.span => ::core::mem::MaybeUninit::as_mut_ptr(#ptr))
         }
+        Some(BaseKind::Ref(kw)) => quote_spanned!(kw.span => ::core::ptr::NonNull::from(#ptr)),
+        Some(BaseKind::Pin(kw)) => {
+            quote_spanned!(kw.span => :: #base_crate ::helper::pinned_base(#ptr))
+        }
+        Some(BaseKind::Erased(..)) | None => ptr.into_token_stream(),
+    };
+    let retype = input
+        .retype
+        .map(|ty| quote_spanned!(ty.span() => let ptr = ptr.cast::<#ty>();));
+
+    // the result is always a raw pointer (or a value read from one),
+    // so keeping the wrapper just means wrapping it again.
+    let ctx = match (input.base_kind, input.keep_pointer) {
+        (Some(BaseKind::Pin(kw)), _) => quote_spanned! { kw.span =>
+            :: #base_crate ::helper::new_pointer({ #ctx }).pin()
+        },
+        (_, Some(..)) => quote!(:: #base_crate ::helper::new_pointer({ #ctx })),
+        (_, None) => ctx.into_token_stream(),
     };
+    let ctx = null_check_block(&input.body, ctx);
+
+    quote! {
+        {
+            let ptr = #ptr;
+            :: #base_crate ::helper::element_ptr_unsafe();
+            #[allow(unused_unsafe)]
+            unsafe {
+                let ptr = :: #base_crate ::helper::new_base_pointer(ptr);
+                #retype
+                let #base = ptr;
+                #ctx
+            }
+        }
+    }
+}
+
+#[proc_macro]
+pub fn element_offset(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as TypedMacroInput);
+
+    if let Some(span) = input.body.null_check() {
+        return syn::Error::new(span, "`element_offset!` cannot check for null pointers")
+            .into_compile_error()
+            .into();
+    }
 
-    let base_crate = Ident::new(&base_crate, Span::call_site());
+    let base_crate = base_crate();
 
     let ctx = AccessListToTokensCtx {
         list: &input.body,
         base_crate: &base_crate,
     };
 
+    let ty = input.ty;
+    let record_base = record_base();
+
+    // everything here is const so that this can be used to initialize constants.
+    (quote! {
+        {
+            let base = ::core::mem::MaybeUninit::<#ty>::uninit();
+            let base = base.as_ptr();
+            :: #base_crate ::helper::element_ptr_unsafe();
+            #[allow(unused_unsafe)]
+            unsafe {
+                let ptr = {
+                    let ptr = :: #base_crate ::helper::new_pointer(base);
+                    let #record_base = ptr;
+                    #ctx
+                };
+                ptr.cast::<u8>().offset_from(base.cast::<u8>()) as usize
+            }
+        }
+    })
+    .into()
+}
+
+#[proc_macro]
+pub fn element_offset_from(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as OffsetFromMacroInput);
+
+    if let Some(span) = input.from.null_check().or(input.to.null_check()) {
+        return syn::Error::new(
+            span,
+            "`element_offset_from!` cannot check for null pointers",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    let base_crate = base_crate();
+
+    let from_ctx = AccessListToTokensCtx {
+        list: &input.from,
+        base_crate: &base_crate,
+    };
+    let to_ctx = AccessListToTokensCtx {
+        list: &input.to,
+        base_crate: &base_crate,
+    };
+
     let ptr = input.ptr;
+    let base = record_base();
+    // these can't be referred to by the accesses, for the same reason as `record_base`.
+    let from = Ident::new("from", Span::mixed_site());
+    let to = Ident::new("to", Span::mixed_site());
 
+    // both sides start from the same base pointer, which is only evaluated once.
     (quote! {
         {
             let ptr = #ptr;
             :: #base_crate ::helper::element_ptr_unsafe();
             #[allow(unused_unsafe)]
             unsafe {
-                let ptr = :: #base_crate ::helper::new_pointer(ptr);
-                #ctx
+                let ptr = :: #base_crate ::helper::new_base_pointer(ptr);
+                let #base = ptr;
+                let #from = :: #base_crate ::helper::new_pointer({ #from_ctx });
+                let #to = :: #base_crate ::helper::new_pointer({ #to_ctx });
+                #to.byte_offset_from(#from)
             }
         }
     })
     .into()
 }
 
+#[proc_macro]
+pub fn element_projector(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut input = parse_macro_input!(input as TypedMacroInput);
+
+    let base_crate = base_crate();
+
+    let mut indices = Vec::new();
+    input.body.replace_placeholders(&mut indices);
+
+    let ctx = AccessListToTokensCtx {
+        list: &input.body,
+        base_crate: &base_crate,
+    };
+
+    let ctx = null_check_block(&input.body, ctx.into_token_stream());
+
+    let ty = input.ty;
+    let index_types = indices.iter().map(|_| quote!(usize));
+    let base = record_base();
+
+    // the closure doesn't capture anything, so it can be coerced to an `unsafe fn`.
+    (quote! {
+        (
+            |ptr: #ty, #(#indices: usize),*| {
+                #[allow(unused_unsafe)]
+                unsafe {
+                    let ptr = :: #base_crate ::helper::new_base_pointer(ptr);
+                    let #base = ptr;
+                    #ctx
+                }
+            }
+        ) as unsafe fn(#ty, #(#index_types),*) -> _
+    })
+    .into()
+}
+
+#[proc_macro]
+pub fn element_ref(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as RefMacroInput);
+
+    let place = input.place;
+    let mut place = quote!((#place));
+    // set after a `.*`, which reads the reference at `place` instead of borrowing it again.
+    let mut read = false;
+
+    for access in &input.body {
+        // anything after a `.*` projects through the reference that was read.
+        let deref = matches!(
+            access,
+            ElementAccess::Field(FieldAccess {
+                field: Some(FieldAccessType::Deref(..)),
+                ..
+            })
+        );
+        if read {
+            place = quote!((*#place));
+        }
+        read = deref;
+
+        match access {
+            ElementAccess::Field(FieldAccess { field, .. }) => match field {
+                Some(FieldAccessType::Named(ident)) => place = quote!(#place.#ident),
+                Some(FieldAccessType::Tuple(index)) => place = quote!(#place.#index),
+                _ => {}
+            },
+            ElementAccess::Index(IndexAccess {
+                index: Some(index), ..
+            }) => place = quote!(#place[#index]),
+            // everything else was rejected while parsing.
+            _ => {}
+        }
+    }
+
+    let amp = input.amp;
+    let mutability = input.mutability;
+    (quote! {
+        (#amp #mutability #place)
+    })
+    .into()
+}
+
+fn base_crate() -> Ident {
+    let found = proc_macro_crate::crate_name("element-ptr").unwrap_or(FoundCrate::Itself);
+
+    let name = match found {
+        FoundCrate::Itself => String::from("element_ptr"),
+        FoundCrate::Name(name) => name,
+    };
+
+    Ident::new(&name, Span::call_site())
+}
+
+/// The name of the base pointer that `record(..)` measures from.
+///
+/// This uses `Span::mixed_site()` so that it can't be referred to by
+/// (or conflict with) any names passed in to the macro.
+fn record_base() -> Ident {
+    Ident::new("base", Span::mixed_site())
+}
+
+/// The label of the block that a `.?` access breaks out of.
+///
+/// This uses `Span::mixed_site()` for the same reason as `record_base`.
+fn null_check_label() -> Lifetime {
+    Lifetime::new("'null_check", Span::mixed_site())
+}
+
+/// Wraps `expr` in a block that any `.?` accesses in `list` can break out of,
+/// which makes it evaluate to an `Option`.
+fn null_check_block(list: &AccessList, expr: TokenStream) -> TokenStream {
+    if list.null_check().is_none() {
+        return expr;
+    }
+    let label = null_check_label();
+    quote! {
+        #label: {
+            ::core::option::Option::Some({ #expr })
+        }
+    }
+}
+
 struct AccessList(Vec<ElementAccess>);
 
 struct AccessListToTokensCtx<'i> {
@@ -60,76 +326,325 @@ impl<'i> ToTokens for AccessListToTokensCtx<'i> {
     fn to_tokens(&self, mut tokens: &mut TokenStream) {
         let base_crate = self.base_crate;
 
-        let mut dirty = false;
+        // set when `ptr` is no longer a `Pointer`, and holds the span
+        // of the access to blame if it turns out to not be a pointer at all.
+        let mut dirty: Option<Span> = None;
+
+        let accesses = &self.list.0;
 
-        for access in &self.list.0 {
+        for (i, access) in accesses.iter().enumerate() {
             use ElementAccess::*;
 
-            if dirty {
-                quote_into! { tokens =>
-                    let ptr = :: #base_crate ::helper::new_pointer(ptr);
+            if let Some(span) = dirty.take() {
+                // only borrow the location, since the compiler suggests
+                // borrowing the access instead of giving the real error otherwise.
+                let span = span.resolved_at(Span::call_site());
+                quote_spanned_into! { tokens, span =>
+                    let ptr = :: #base_crate ::helper::new_read_pointer(ptr);
                 };
-                dirty = false;
             }
 
             match access {
-                Field(FieldAccess { _dot, field }) => match &field {
-                    Some(FieldAccessType::Named(ident)) => quote_into! { tokens =>
-                        let ptr = ptr.copy_addr(
-                            ::core::ptr::addr_of!( ( *ptr.into_const() ) . #ident )
-                        );
-                    },
-                    Some(FieldAccessType::Tuple(index)) => quote_into! { tokens =>
-                        let ptr = ptr.copy_addr(
-                            ::core::ptr::addr_of!( ( *ptr.into_const() ) . #index )
-                        );
-                    },
-                    Some(FieldAccessType::Deref(..)) => {
-                        dirty = true;
+                Field(FieldAccess { _dot, field, ty }) => {
+                    // fields are always projected from a `*mut T`, so that the pointer to
+                    // the field keeps coming from a `*mut T` if the base was writable.
+                    // the provenance is the same either way, so this is fine for `*const T`.
+                    match &field {
+                        Some(FieldAccessType::Named(ident)) => quote_into! { tokens =>
+                            let ptr = ptr.copy_addr(
+                                ::core::ptr::addr_of_mut!( ( *ptr.into_mut() ) . #ident )
+                            );
+                        },
+                        Some(FieldAccessType::Tuple(index)) => quote_into! { tokens =>
+                            let ptr = ptr.copy_addr(
+                                ::core::ptr::addr_of_mut!( ( *ptr.into_mut() ) . #index )
+                            );
+                        },
+                        Some(FieldAccessType::Deref(star, kind)) => {
+                            dirty = Some(star.span);
+                            let read = match kind {
+                                Some(ReadKind::Volatile(kw)) => {
+                                    Ident::new("read_volatile", kw.span)
+                                }
+                                Some(ReadKind::Unaligned(kw)) => {
+                                    Ident::new("read_unaligned", kw.span)
+                                }
+                                None => Ident::new("read", star.span),
+                            };
+                            quote_into! { tokens =>
+                                let ptr = ptr.#read();
+                            }
+                        }
+                        Some(FieldAccessType::Ref {
+                            amp, mutability, ..
+                        }) => {
+                            dirty = Some(amp.span);
+                            // raw pointers give an `Option`, and `NonNull` gives a reference.
+                            match mutability {
+                                // point the error for `*const T` at the `&`.
+                                Some(mutability) => {
+                                    let as_mut = Ident::new("as_mut", mutability.span);
+                                    quote_spanned_into! { tokens, amp.span =>
+                                        let ptr = :: #base_crate ::helper::into_writable(ptr).#as_mut();
+                                    };
+                                }
+                                None => {
+                                    let as_ref = Ident::new("as_ref", amp.span);
+                                    quote_into! { tokens =>
+                                        let ptr = ptr.into_inner().#as_ref();
+                                    }
+                                }
+                            }
+                        }
+                        // output something for r-a autocomplete.
+                        None => {
+                            // honestly i'm not quite sure why this specifically
+                            // lets r-a autocomplete after the dot, but it does, and also
+                            // gives a correct (and sort of fake) compiler error of
+                            // "unexpected token `)`".
+                            // i wish there was a better way to interact with r-a about this,
+                            // but this hack will have to do.
+                            let error = syn::Error::new_spanned(
+                                _dot,
+                                "expected an identifier, integer literal, `*`, or `&` after this `.`",
+                            )
+                            .into_compile_error();
+                            quote_into! { tokens =>
+                                let ptr = ptr.copy_addr(
+                                    ::core::ptr::addr_of_mut!( ( *ptr.into_mut() ) #_dot )
+                                );
+                                #error;
+                            }
+                            // just stop generating from here.
+                            return;
+                        }
+                    }
+                    // the type is asserted by just giving the pointer that type.
+                    if let Some(FieldType { ty, .. }) = ty {
+                        quote_spanned_into! { tokens, ty.span() =>
+                            let ptr: :: #base_crate ::helper::Pointer<_, #ty> = ptr;
+                        };
+                    }
+                }
+                Index(IndexAccess { _bracket, index }) => match index {
+                    // `[start..end]` makes a slice pointer instead of indexing.
+                    Some(Expr::Range(range)) => {
+                        let start = match &range.start {
+                            Some(start) => quote!(#start),
+                            None => quote!(0),
+                        };
+                        // open ended ranges only work if the pointer knows its length.
+                        let (method, end) = match (&range.end, &range.limits) {
+                            (Some(end), RangeLimits::HalfOpen(..)) => ("slice_range", quote!(#end)),
+                            // `end + 1` could overflow, so this is checked by the helper.
+                            (Some(end), RangeLimits::Closed(..)) => {
+                                ("slice_range_inclusive", quote!(#end))
+                            }
+                            (None, _) => ("slice_range", quote!(ptr.len())),
+                        };
+                        let method = Ident::new(method, Span::call_site());
                         quote_into! { tokens =>
-                            let ptr = ptr.read();
+                            let ptr = ptr.#method(#start, #end);
                         }
                     }
-                    // output something for r-a autocomplete.
+                    Some(index) => quote_into! { tokens =>
+                        let ptr = :: #base_crate ::helper::index(ptr, #index);
+                    },
+                    // same idea as the empty field access.
                     None => {
-                        // honestly i'm not quite sure why this specifically
-                        // lets r-a autocomplete after the dot, but it does, and also
-                        // gives a correct (and sort of fake) compiler error of
-                        // "unexpected token `)`".
-                        // i wish there was a better way to interact with r-a about this,
-                        // but this hack will have to do.
-                        let error = syn::Error::new_spanned(
-                            _dot,
-                            "expected an identifier, integer literal, or `*` after this `.`",
+                        let error = syn::Error::new(
+                            _bracket.span.join(),
+                            "expected an index expression inside of these brackets",
                         )
                         .into_compile_error();
                         quote_into! { tokens =>
-                            let ptr = ptr.copy_addr(
-                                ::core::ptr::addr_of!( ( *ptr.into_const() ) #_dot )
-                            );
-                            #error;
+                            let ptr = :: #base_crate ::helper::index(ptr, #error);
                         }
                         // just stop generating from here.
                         return;
                     }
                 },
-                Index(IndexAccess { index, .. }) => quote_into! { tokens =>
-                    let ptr = :: #base_crate ::helper::index(ptr, #index);
+                MultiIndex(MultiIndexAccess { row, col, .. }) => quote_into! { tokens =>
+                    let ptr = :: #base_crate ::helper::multi_index(ptr, #row, #col);
                 },
+                Split(SplitAccess { mid, .. }) => {
+                    dirty = Some(Span::call_site());
+                    quote_into! { tokens =>
+                        let ptr = {
+                            let (left, right) = ptr.split_at(#mid);
+                            (left.into_inner(), right.into_inner())
+                        };
+                    }
+                }
+                PrefixedSlice(access) => {
+                    let PrefixedSliceAccess {
+                        len_ty, elem_ty, ..
+                    } = &**access;
+                    let elem_ty = match elem_ty {
+                        Some((_, ty)) => ty.into_token_stream(),
+                        None => quote!(_),
+                    };
+                    quote_into! { tokens =>
+                        let ptr = ptr.prefixed_slice::<#len_ty, #elem_ty>();
+                    }
+                }
+                Query(QueryAccess { kind, .. }) => {
+                    let method = match kind {
+                        QueryKind::Len(kw) => Ident::new("len", kw.span),
+                        QueryKind::Addr(kw) => Ident::new("addr", kw.span),
+                    };
+                    dirty = Some(method.span());
+                    quote_into! { tokens =>
+                        let ptr = ptr.#method();
+                    }
+                }
+                Unwrap(UnwrapAccess { kind, .. }) => {
+                    let method = match kind {
+                        UnwrapKind::Cell(kw) => Ident::new("cell_inner", kw.span),
+                        UnwrapKind::Uninit(kw) => Ident::new("uninit_inner", kw.span),
+                        UnwrapKind::ManuallyDrop(kw) => Ident::new("manually_drop_inner", kw.span),
+                        UnwrapKind::Aligned(kw) => {
+                            Ident::new("debug_assert_pointee_aligned", kw.span)
+                        }
+                    };
+                    quote_into! { tokens =>
+                        let ptr = ptr.#method();
+                    }
+                    if let UnwrapKind::Aligned(kw) = kind {
+                        // a `#[repr(align(N))]` wrapper can't be `#[repr(transparent)]`,
+                        // so its field is projected like any other tuple field.
+                        let index = syn::Index {
+                            index: 0,
+                            span: kw.span,
+                        };
+                        quote_into! { tokens =>
+                            let ptr = ptr.copy_addr(
+                                ::core::ptr::addr_of_mut!( ( *ptr.into_mut() ) . #index )
+                            );
+                        }
+                    }
+                }
+                Record(RecordAccess { target, .. }) => {
+                    let base = record_base();
+                    quote_into! { tokens =>
+                        *(#target) = ptr
+                            .into_const()
+                            .cast::<u8>()
+                            .offset_from(#base.into_const().cast::<u8>()) as usize;
+                    }
+                }
+                Align(AlignAccess { kind, align, .. }) => match kind {
+                    AlignKind::Check(name) => {
+                        let check = Ident::new("debug_assert_aligned", name.span);
+                        quote_into! { tokens =>
+                            let ptr = ptr.#check(#align);
+                        }
+                    }
+                    AlignKind::Up(name) => {
+                        let align_offset = Ident::new("align_offset", name.span);
+                        quote_into! { tokens =>
+                            let ptr = {
+                                let offset = ptr.#align_offset(#align);
+                                // the pointer is left alone if it can't be aligned.
+                                if offset == ::core::primitive::usize::MAX {
+                                    ptr
+                                } else {
+                                    ptr.add(offset)
+                                }
+                            };
+                        }
+                    }
+                },
+                NullCheck(NullCheckAccess { question, .. }) => {
+                    let is_null = Ident::new("is_null", question.span);
+                    let label = null_check_label();
+                    quote_into! { tokens =>
+                        if ptr.#is_null() {
+                            break #label ::core::option::Option::None;
+                        }
+                    }
+                }
+                Addr(AddrAccess { kind, value, .. }) => {
+                    let method = match kind {
+                        AddrKind::With(kw) => Ident::new("with_addr", kw.span),
+                        AddrKind::Map(kw) => Ident::new("map_addr", kw.span),
+                    };
+                    quote_into! { tokens =>
+                        let ptr = ptr.#method(#value);
+                    }
+                }
+                Write(WriteAccess {
+                    _arrow,
+                    kind,
+                    value,
+                }) => {
+                    dirty = Some(_arrow.spans[0]);
+                    let write = match kind {
+                        Some(WriteKind::Volatile(kw)) => Ident::new("write_volatile", kw.span),
+                        Some(WriteKind::Unaligned(kw)) => Ident::new("write_unaligned", kw.span),
+                        None => Ident::new("write", _arrow.spans[0]),
+                    };
+                    // point the error for writing through a `*const T` at the `<-`.
+                    quote_spanned_into! { tokens, _arrow.spans[0] =>
+                        let ptr = :: #base_crate ::helper::#write(ptr, #value);
+                    };
+                }
                 Offset(access) => {
-                    let name = match (&access.offset_type, access.byte.is_some()) {
-                        (OffsetType::Add(..), false) => Ident::new("add", Span::call_site()),
-                        (OffsetType::Sub(..), false) => Ident::new("sub", Span::call_site()),
-                        (OffsetType::Add(..), true) => Ident::new("byte_add", Span::call_site()),
-                        (OffsetType::Sub(..), true) => Ident::new("byte_sub", Span::call_site()),
+                    let (name, span) = match (&access.offset_type, access.byte.is_some()) {
+                        (OffsetType::Add(plus), false) => ("add", plus.span),
+                        (OffsetType::Sub(minus), false) => ("sub", minus.span),
+                        (OffsetType::Add(plus), true) => ("byte_add", plus.span),
+                        (OffsetType::Sub(minus), true) => ("byte_sub", minus.span),
+                        (OffsetType::Signed(at), false) => ("offset", at.span),
+                        (OffsetType::Signed(at), true) => ("byte_offset", at.span),
+                    };
+                    let name = match access.wrapping {
+                        Some(_) => format_ident!("wrapping_{}", name, span = span),
+                        None => Ident::new(name, span),
                     };
                     let offset = &access.value;
                     quote_into! { tokens =>
                         let ptr = ptr . #name ( #offset );
                     }
                 }
-                Cast(CastAccess { ty, .. }) => quote_into! { tokens =>
-                    let ptr = ptr.cast::<#ty>();
+                Cast(CastAccess {
+                    ty,
+                    mutability,
+                    read,
+                    ..
+                }) => match read {
+                    Some((_, star)) => {
+                        dirty = Some(star.span);
+                        let cast_read = Ident::new("cast_read", star.span);
+                        quote_into! { tokens =>
+                            let ptr = ptr.#cast_read::<#ty>();
+                        }
+                    }
+                    None => {
+                        if let Some(mutability) = mutability {
+                            let method = match mutability {
+                                CastMutability::Mut(kw) => Ident::new("cast_mut", kw.span),
+                                CastMutability::Const(kw) => Ident::new("cast_const", kw.span),
+                            };
+                            quote_into! { tokens =>
+                                let ptr = ptr.#method();
+                            }
+                        }
+                        // casting thin pointers is transitive, so only the last
+                        // of a run of casts needs to be emitted. the others are
+                        // still type checked, without doing anything at runtime.
+                        if let Some(Cast(..)) = accesses.get(i + 1) {
+                            quote_into! { tokens =>
+                                if false {
+                                    let _ = ptr.cast::<#ty>();
+                                }
+                            }
+                            continue;
+                        }
+                        quote_into! { tokens =>
+                            let ptr = ptr.cast::<#ty>();
+                        }
+                    }
                 },
                 Group(access) => {
                     let list = AccessListToTokensCtx {
@@ -141,11 +656,24 @@ impl<'i> ToTokens for AccessListToTokensCtx<'i> {
                             #list
                         };
                     };
-                    dirty = true;
+                    dirty = Some(access._paren.span.join());
+                }
+                Let(LetAccess { name, inner, .. }) => {
+                    // the inner accesses start from the current pointer,
+                    // but the pointer itself is left where it is.
+                    let list = AccessListToTokensCtx {
+                        list: inner,
+                        base_crate: self.base_crate,
+                    };
+                    quote_into! { tokens =>
+                        let #name = {
+                            #list
+                        };
+                    };
                 }
             };
         }
-        if dirty {
+        if dirty.is_some() {
             quote_into! { tokens =>
                 ptr
             };
@@ -157,12 +685,13 @@ impl<'i> ToTokens for AccessListToTokensCtx<'i> {
     }
 }
 
-impl Parse for AccessList {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
+impl AccessList {
+    /// Parses accesses until either the input is empty or `end` returns true.
+    fn parse_until(input: ParseStream, end: fn(ParseStream) -> bool) -> syn::Result<Self> {
         let mut out = Vec::new();
-        while !input.is_empty() {
+        while !input.is_empty() && !end(input) {
             let access: ElementAccess = input.parse()?;
-            if access.is_final() && !input.is_empty() {
+            if access.is_final() && !input.is_empty() && !end(input) {
                 return Err(input.error(""));
             }
             out.push(access);
@@ -171,34 +700,296 @@ impl Parse for AccessList {
     }
 }
 
+impl AccessList {
+    /// Returns the span of the first `.?` access, including ones inside of groups and bindings.
+    fn null_check(&self) -> Option<Span> {
+        self.0.iter().find_map(|access| match access {
+            ElementAccess::NullCheck(access) => Some(access.question.span),
+            ElementAccess::Group(access) => access.inner.null_check(),
+            ElementAccess::Let(access) => access.inner.null_check(),
+            _ => None,
+        })
+    }
+
+    /// Returns the span of the first `.*` or `as T.*` access, including ones inside of groups
+    /// and bindings.
+    fn read(&self) -> Option<Span> {
+        self.0.iter().find_map(|access| match access {
+            ElementAccess::Field(FieldAccess {
+                field: Some(FieldAccessType::Deref(star, ..)),
+                ..
+            }) => Some(star.span),
+            ElementAccess::Cast(CastAccess {
+                read: Some((_, star)),
+                ..
+            }) => Some(star.span),
+            ElementAccess::Group(access) => access.inner.read(),
+            ElementAccess::Let(access) => access.inner.read(),
+            _ => None,
+        })
+    }
+
+    /// Replaces every `[_]` index with a new identifier, which is pushed to `indices`.
+    fn replace_placeholders(&mut self, indices: &mut Vec<Ident>) {
+        for access in &mut self.0 {
+            match access {
+                ElementAccess::Index(IndexAccess {
+                    index: Some(index), ..
+                }) => replace_placeholder(index, indices),
+                ElementAccess::MultiIndex(MultiIndexAccess { row, col, .. }) => {
+                    replace_placeholder(row, indices);
+                    replace_placeholder(col, indices);
+                }
+                ElementAccess::Group(GroupAccess { inner, .. })
+                | ElementAccess::Let(LetAccess { inner, .. }) => {
+                    inner.replace_placeholders(indices)
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Replaces `index` with a new identifier if it is `_`, which is pushed to `indices`.
+fn replace_placeholder(index: &mut Expr, indices: &mut Vec<Ident>) {
+    if let Expr::Infer(..) = index {
+        let ident = Ident::new(&format!("index{}", indices.len()), index.span());
+        *index = syn::parse_quote!(#ident);
+        indices.push(ident);
+    }
+}
+
+impl Parse for AccessList {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Self::parse_until(input, |_| false)
+    }
+}
+
 struct MacroInput {
+    base_kind: Option<BaseKind>,
     ptr: Expr,
+    // the `T` in an `erased handle as T` base.
+    retype: Option<Box<Type>>,
     _arrow: Token![=>],
     body: AccessList,
+    keep_pointer: Option<Token![~]>,
 }
 
 impl Parse for MacroInput {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        // `uninit` and `pin` could also just be the name of a variable,
+        // so only treat them as keywords if an expression follows them.
+        fn is_keyword<K: Parse>(input: ParseStream) -> bool {
+            let fork = input.fork();
+            fork.parse::<K>().is_ok() && fork.parse::<Expr>().is_ok() && fork.peek(Token![=>])
+        }
+        let base_kind = if input.peek(kw::uninit) && is_keyword::<kw::uninit>(input) {
+            Some(BaseKind::Uninit(input.parse()?))
+        } else if input.peek(kw::pin) && is_keyword::<kw::pin>(input) {
+            Some(BaseKind::Pin(input.parse()?))
+        } else if input.peek(kw::erased) && is_keyword::<kw::erased>(input) {
+            Some(BaseKind::Erased(input.parse()?))
+        } else if input.peek(Token![ref]) {
+            Some(BaseKind::Ref(input.parse()?))
+        } else {
+            None
+        };
+        let (ptr, retype) = match (&base_kind, input.parse()?) {
+            // after `erased`, the cast always gives the type that is pointed to,
+            // even if that type is itself a pointer.
+            (Some(BaseKind::Erased(..)), Expr::Cast(cast)) => (*cast.expr, Some(cast.ty)),
+            (Some(BaseKind::Erased(kw)), _) => {
+                return Err(syn::Error::new(
+                    kw.span,
+                    "an `erased` base must end with `as T`, where `T` is the type that it points to",
+                ));
+            }
+            (_, ptr) => (ptr, None),
+        };
+        let _arrow = input.parse()?;
+        let body = AccessList::parse_until(input, |input| {
+            input.peek(Token![~]) && !OffsetAccess::peek_wrapping(input)
+        })?;
+        let keep_pointer: Option<Token![~]> = input.parse()?;
+        if let Some(BaseKind::Pin(kw)) = base_kind {
+            // whatever a pinned value points to is not pinned itself.
+            if let Some(span) = body.read() {
+                return Err(syn::Error::new(
+                    span,
+                    "a `pin` projection cannot use `.*`, since the value that is read is not pinned",
+                ));
+            }
+            if body.0.last().is_some_and(ElementAccess::is_final) {
+                return Err(syn::Error::new(
+                    kw.span,
+                    "a `pin` projection must end at a pointer to keep it pinned",
+                ));
+            }
+            if let Some(tilde) = keep_pointer {
+                return Err(syn::Error::new(
+                    tilde.span,
+                    "a `pin` projection always returns a pinned pointer, so it cannot be kept with `~`",
+                ));
+            }
+        }
         Ok(Self {
-            ptr: input.parse()?,
+            base_kind,
+            ptr,
+            retype,
+            _arrow,
+            body,
+            keep_pointer,
+        })
+    }
+}
+
+enum BaseKind {
+    // `uninit slot`, which starts from a `&mut MaybeUninit<T>`.
+    Uninit(kw::uninit),
+    // `ref value`, which starts from a `&T` or `&mut T` as a `NonNull<T>`.
+    Ref(Token![ref]),
+    // `erased handle as T`, which starts from any pointer as a pointer to `T`.
+    Erased(kw::erased),
+    // `pin pinned`, which starts from a `PinnedPointer`, `Pin<&T>`, or `Pin<&mut T>`
+    // and keeps the result pinned.
+    Pin(kw::pin),
+}
+
+struct TypedMacroInput {
+    ty: Type,
+    _arrow: Token![=>],
+    body: AccessList,
+}
+
+impl Parse for TypedMacroInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            ty: input.parse()?,
             _arrow: input.parse()?,
             body: input.parse()?,
         })
     }
 }
 
+struct OffsetFromMacroInput {
+    ptr: Expr,
+    _arrow: Token![=>],
+    from: AccessList,
+    _semi: Token![;],
+    to: AccessList,
+}
+
+impl Parse for OffsetFromMacroInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            ptr: input.parse()?,
+            _arrow: input.parse()?,
+            from: AccessList::parse_until(input, |input| input.peek(Token![;]))?,
+            _semi: input.parse()?,
+            to: input.parse()?,
+        })
+    }
+}
+
+struct RefMacroInput {
+    amp: Token![&],
+    mutability: Option<Token![mut]>,
+    place: Expr,
+    _arrow: Token![=>],
+    body: Vec<ElementAccess>,
+}
+
+impl Parse for RefMacroInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let base: Expr = input.parse()?;
+        let Expr::Reference(base) = base else {
+            return Err(syn::Error::new_spanned(
+                base,
+                "the base of `element_ref!` must be a borrow, like `&value` or `&mut value`",
+            ));
+        };
+        let _arrow = input.parse()?;
+        let mut body = Vec::new();
+        while !input.is_empty() {
+            let span = input.span();
+            let access = input.parse()?;
+            // only the accesses that are also valid in a place expression can be used,
+            // so that the borrow checker can verify the whole projection.
+            match &access {
+                ElementAccess::Field(FieldAccess { ty: Some(ty), .. }) => {
+                    return Err(syn::Error::new(
+                        ty._colon.span,
+                        "`element_ref!` cannot assert the types of fields",
+                    ));
+                }
+                ElementAccess::Field(FieldAccess {
+                    field:
+                        Some(
+                            FieldAccessType::Named(..)
+                            | FieldAccessType::Tuple(..)
+                            | FieldAccessType::Deref(_, None),
+                        ),
+                    ..
+                })
+                | ElementAccess::Index(IndexAccess {
+                    index: Some(..), ..
+                }) => {}
+                _ => {
+                    return Err(syn::Error::new(
+                        span,
+                        "`element_ref!` only supports field, index, and `.*` accesses",
+                    ));
+                }
+            }
+            body.push(access);
+        }
+        // a final `.*` would return the value instead of a reference to it.
+        if let Some(ElementAccess::Field(FieldAccess {
+            field: Some(FieldAccessType::Deref(star, _)),
+            ..
+        })) = body.last()
+        {
+            return Err(syn::Error::new(
+                star.span,
+                "`element_ref!` always returns a reference, so it cannot end with `.*`",
+            ));
+        }
+        Ok(Self {
+            amp: base.and_token,
+            mutability: base.mutability,
+            place: *base.expr,
+            _arrow,
+            body,
+        })
+    }
+}
+
 enum ElementAccess {
     Field(FieldAccess),
     Index(IndexAccess),
+    MultiIndex(MultiIndexAccess),
+    Split(SplitAccess),
+    // boxed because it is much larger than every other access.
+    PrefixedSlice(Box<PrefixedSliceAccess>),
+    Query(QueryAccess),
+    NullCheck(NullCheckAccess),
+    Unwrap(UnwrapAccess),
+    Record(RecordAccess),
+    Align(AlignAccess),
+    Addr(AddrAccess),
+    Write(WriteAccess),
     Offset(OffsetAccess),
     Cast(CastAccess),
     Group(GroupAccess),
+    Let(LetAccess),
 }
 
 impl ElementAccess {
     fn is_final(&self) -> bool {
         match self {
-            Self::Cast(acc) => acc.arrow.is_none(),
+            Self::Cast(acc) => acc.arrow.is_none() && acc.read.is_none(),
+            Self::Field(acc) => matches!(acc.field, Some(FieldAccessType::Ref { .. })),
+            Self::Split(..) | Self::Write(..) | Self::Query(..) => true,
             _ => false,
         }
     }
@@ -206,16 +997,52 @@ impl ElementAccess {
 
 impl Parse for ElementAccess {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        if input.peek(Token![.]) {
+        if input.peek(Token![.]) && input.peek2(kw::prefixed_slice) && input.peek3(Token![::]) {
+            input.parse().map(Self::PrefixedSlice)
+        } else if input.peek(Token![.])
+            && (input.peek2(kw::len) || input.peek2(kw::addr))
+            && input.peek3(token::Paren)
+        {
+            input.parse().map(Self::Query)
+        } else if input.peek(Token![.]) && input.peek2(Token![?]) {
+            input.parse().map(Self::NullCheck)
+        } else if input.peek(Token![.]) && input.peek2(token::Brace) {
+            input.parse().map(Self::Unwrap)
+        } else if input.peek(Token![.]) {
             input.parse().map(Self::Field)
         } else if input.peek(token::Bracket) {
-            input.parse().map(Self::Index)
-        } else if input.peek(kw::u8) || input.peek(Token![+]) || input.peek(Token![-]) {
+            if SplitAccess::peek(input) {
+                input.parse().map(Self::Split)
+            } else if MultiIndexAccess::peek(input) {
+                input.parse().map(Self::MultiIndex)
+            } else {
+                input.parse().map(Self::Index)
+            }
+        } else if input.peek(Token![<-]) {
+            input.parse().map(Self::Write)
+        } else if input.peek(kw::u8)
+            || input.peek(Token![+])
+            || input.peek(Token![-])
+            || input.peek(Token![@])
+            || OffsetAccess::peek_wrapping(input)
+        {
             input.parse().map(Self::Offset)
         } else if input.peek(Token![as]) {
             input.parse().map(Self::Cast)
         } else if input.peek(token::Paren) {
             input.parse().map(Self::Group)
+        } else if input.peek(Token![let]) {
+            input.parse().map(Self::Let)
+        } else if input.peek(kw::record) && input.peek2(token::Paren) {
+            input.parse().map(Self::Record)
+        } else if (input.peek(kw::align) || input.peek(kw::assert_align))
+            && input.peek2(token::Paren)
+        {
+            input.parse().map(Self::Align)
+        } else if (input.peek(kw::with_addr) || input.peek(kw::map_addr))
+            && input.peek2(token::Paren)
+        {
+            input.parse().map(Self::Addr)
         } else {
             Err(input.error("expected valid element access"))
         }
@@ -226,19 +1053,42 @@ impl Parse for ElementAccess {
 struct FieldAccess {
     _dot: Token![.],
     field: Option<FieldAccessType>,
+    ty: Option<FieldType>,
 }
 
 impl Parse for FieldAccess {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let _dot = input.parse()?;
+        let field = {
+            if input.is_empty() {
+                None
+            } else {
+                Some(input.parse()?)
+            }
+        };
+        let ty = match field {
+            Some(FieldAccessType::Named(..) | FieldAccessType::Tuple(..))
+                if input.peek(Token![:]) && !input.peek(Token![::]) =>
+            {
+                Some(input.parse()?)
+            }
+            _ => None,
+        };
+        Ok(Self { _dot, field, ty })
+    }
+}
+
+struct FieldType {
+    _colon: Token![:],
+    ty: Type,
+}
+
+impl Parse for FieldType {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         Ok(Self {
-            _dot: input.parse()?,
-            field: {
-                if input.is_empty() {
-                    None
-                } else {
-                    Some(input.parse()?)
-                }
-            },
+            _colon: input.parse()?,
+            // a `+` after the type is an offset, not a trait bound.
+            ty: Type::without_plus(input)?,
         })
     }
 }
@@ -246,14 +1096,33 @@ impl Parse for FieldAccess {
 enum FieldAccessType {
     Named(Ident),
     Tuple(Index),
-    Deref(Token![*]),
+    // `.*`, `.*volatile`, or `.*unaligned`
+    Deref(Token![*], Option<ReadKind>),
+    // `.&?` or `.&mut?`
+    Ref {
+        amp: Token![&],
+        mutability: Option<Token![mut]>,
+        _question: Token![?],
+    },
 }
 
 impl Parse for FieldAccessType {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let l = input.lookahead1();
         if l.peek(Token![*]) {
-            input.parse().map(Self::Deref)
+            let star = input.parse()?;
+            let kind = if input.peek(kw::volatile) || input.peek(kw::unaligned) {
+                Some(input.parse()?)
+            } else {
+                None
+            };
+            Ok(Self::Deref(star, kind))
+        } else if l.peek(Token![&]) {
+            Ok(Self::Ref {
+                amp: input.parse()?,
+                mutability: input.parse()?,
+                _question: input.parse()?,
+            })
         } else if l.peek(syn::Ident) {
             input.parse().map(Self::Named)
         } else if l.peek(LitInt) {
@@ -265,9 +1134,27 @@ impl Parse for FieldAccessType {
     }
 }
 
+enum ReadKind {
+    Volatile(kw::volatile),
+    Unaligned(kw::unaligned),
+}
+
+impl Parse for ReadKind {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let l = input.lookahead1();
+        if l.peek(kw::volatile) {
+            input.parse().map(Self::Volatile)
+        } else if l.peek(kw::unaligned) {
+            input.parse().map(Self::Unaligned)
+        } else {
+            Err(l.error())
+        }
+    }
+}
+
 struct IndexAccess {
     _bracket: token::Bracket,
-    index: Expr,
+    index: Option<Expr>,
 }
 
 impl Parse for IndexAccess {
@@ -275,11 +1162,441 @@ impl Parse for IndexAccess {
         let content;
         Ok(Self {
             _bracket: bracketed!(content in input),
-            index: content.parse()?,
+            index: {
+                if content.is_empty() {
+                    None
+                } else {
+                    Some(content.parse()?)
+                }
+            },
+        })
+    }
+}
+
+// `[row, col]`
+struct MultiIndexAccess {
+    _bracket: token::Bracket,
+    row: Expr,
+    _comma: Token![,],
+    col: Expr,
+}
+
+impl MultiIndexAccess {
+    /// Returns whether the brackets contain an expression followed by a `,`.
+    fn peek(input: ParseStream) -> bool {
+        let check = || -> syn::Result<bool> {
+            let fork = input.fork();
+            let content;
+            bracketed!(content in fork);
+            content.parse::<Expr>()?;
+            Ok(content.peek(Token![,]))
+        };
+        check().unwrap_or(false)
+    }
+}
+
+impl Parse for MultiIndexAccess {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        Ok(Self {
+            _bracket: bracketed!(content in input),
+            row: content.parse()?,
+            _comma: content.parse()?,
+            col: content.parse()?,
+        })
+    }
+}
+
+// `[..mid | mid..]`
+struct SplitAccess {
+    _bracket: token::Bracket,
+    mid: Expr,
+}
+
+impl SplitAccess {
+    /// Returns whether the brackets start with a `..` and contain a `|`.
+    fn peek(input: ParseStream) -> bool {
+        let check = || -> syn::Result<bool> {
+            let fork = input.fork();
+            let content;
+            bracketed!(content in fork);
+            Ok(content.peek(Token![..])
+                && content.step(|cursor| {
+                    let mut rest = *cursor;
+                    while let Some((tt, next)) = rest.token_tree() {
+                        if is_bar(&tt) {
+                            return Ok((true, next));
+                        }
+                        rest = next;
+                    }
+                    Ok((false, rest))
+                })?)
+        };
+        check().unwrap_or(false)
+    }
+}
+
+/// Returns whether `tt` is a lone `|`, and not part of a `||` or `|=`.
+fn is_bar(tt: &TokenTree) -> bool {
+    matches!(tt, TokenTree::Punct(p) if p.as_char() == '|' && p.spacing() == Spacing::Alone)
+}
+
+impl Parse for SplitAccess {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        let _bracket = bracketed!(content in input);
+        content.parse::<Token![..]>()?;
+        let left = content.step(|cursor| {
+            let mut tokens = TokenStream::new();
+            let mut rest = *cursor;
+            while let Some((tt, next)) = rest.token_tree() {
+                if is_bar(&tt) {
+                    return Ok((tokens, rest));
+                }
+                tokens.extend([tt]);
+                rest = next;
+            }
+            Ok((tokens, rest))
+        })?;
+        let bar: Token![|] = content.parse()?;
+        let right = content.step(|cursor| {
+            let mut tokens = TokenStream::new();
+            let mut rest = *cursor;
+            while let Some((tt, next)) = rest.token_tree() {
+                // stop at the final `..`
+                if let Some((dot, after)) = rest.punct() {
+                    if dot.as_char() == '.' && dot.spacing() == Spacing::Joint {
+                        if let Some((dot, after)) = after.punct() {
+                            if dot.as_char() == '.' && after.eof() {
+                                return Ok((tokens, rest));
+                            }
+                        }
+                    }
+                }
+                tokens.extend([tt]);
+                rest = next;
+            }
+            Ok((tokens, rest))
+        })?;
+        content.parse::<Token![..]>()?;
+        if left.to_string() != right.to_string() {
+            return Err(syn::Error::new(
+                bar.span,
+                "both sides of a split must use the same midpoint",
+            ));
+        }
+        Ok(Self {
+            _bracket,
+            mid: syn::parse2(left)?,
+        })
+    }
+}
+
+// `.prefixed_slice::<L>()` or `.prefixed_slice::<L, E>()`
+struct PrefixedSliceAccess {
+    _dot: Token![.],
+    _name: kw::prefixed_slice,
+    _colons: Token![::],
+    _lt: Token![<],
+    len_ty: Type,
+    elem_ty: Option<(Token![,], Type)>,
+    _gt: Token![>],
+    _paren: token::Paren,
+}
+
+impl Parse for PrefixedSliceAccess {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let _dot = input.parse()?;
+        let _name = input.parse()?;
+        let _colons = input.parse()?;
+        let _lt = input.parse()?;
+        let len_ty = input.parse()?;
+        let elem_ty = if input.peek(Token![,]) {
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+        let _gt = input.parse()?;
+        let content;
+        let _paren = parenthesized!(content in input);
+        if !content.is_empty() {
+            return Err(content.error("`prefixed_slice` does not take any arguments"));
+        }
+        Ok(Self {
+            _dot,
+            _name,
+            _colons,
+            _lt,
+            len_ty,
+            elem_ty,
+            _gt,
+            _paren,
+        })
+    }
+}
+
+// `.len()` or `.addr()`
+struct QueryAccess {
+    _dot: Token![.],
+    kind: QueryKind,
+    _paren: token::Paren,
+}
+
+impl Parse for QueryAccess {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let _dot = input.parse()?;
+        let kind = input.parse()?;
+        let content;
+        let _paren = parenthesized!(content in input);
+        if !content.is_empty() {
+            let name = match kind {
+                QueryKind::Len(..) => "len",
+                QueryKind::Addr(..) => "addr",
+            };
+            return Err(content.error(format!("`{name}` does not take any arguments")));
+        }
+        Ok(Self { _dot, kind, _paren })
+    }
+}
+
+enum QueryKind {
+    // `.len()`, which gives the number of elements.
+    Len(kw::len),
+    // `.addr()`, which gives the address without exposing the provenance.
+    Addr(kw::addr),
+}
+
+impl Parse for QueryKind {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let l = input.lookahead1();
+        if l.peek(kw::len) {
+            input.parse().map(Self::Len)
+        } else if l.peek(kw::addr) {
+            input.parse().map(Self::Addr)
+        } else {
+            Err(l.error())
+        }
+    }
+}
+
+// `.?`
+struct NullCheckAccess {
+    _dot: Token![.],
+    question: Token![?],
+}
+
+impl Parse for NullCheckAccess {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            _dot: input.parse()?,
+            question: input.parse()?,
+        })
+    }
+}
+
+// `.{cell}`, `.{uninit}`, `.{md}`, or `.{aligned}`
+struct UnwrapAccess {
+    _dot: Token![.],
+    _brace: token::Brace,
+    kind: UnwrapKind,
+}
+
+impl Parse for UnwrapAccess {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        let access = Self {
+            _dot: input.parse()?,
+            _brace: braced!(content in input),
+            kind: content.parse()?,
+        };
+        if !content.is_empty() {
+            return Err(content.error("expected only one wrapper type inside of these braces"));
+        }
+        Ok(access)
+    }
+}
+
+enum UnwrapKind {
+    // `.{cell}`, which goes into an `UnsafeCell<T>` or `Cell<T>`.
+    Cell(kw::cell),
+    // `.{uninit}`, which goes into a `MaybeUninit<T>`.
+    Uninit(kw::uninit),
+    // `.{md}`, which goes into a `ManuallyDrop<T>`.
+    ManuallyDrop(kw::md),
+    // `.{aligned}`, which goes into the field of a `#[repr(align(N))]` wrapper.
+    Aligned(kw::aligned),
+}
+
+impl Parse for UnwrapKind {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let l = input.lookahead1();
+        if l.peek(kw::cell) {
+            input.parse().map(Self::Cell)
+        } else if l.peek(kw::uninit) {
+            input.parse().map(Self::Uninit)
+        } else if l.peek(kw::md) {
+            input.parse().map(Self::ManuallyDrop)
+        } else if l.peek(kw::aligned) {
+            input.parse().map(Self::Aligned)
+        } else {
+            Err(l.error())
+        }
+    }
+}
+
+// `record(&mut offset)`
+struct RecordAccess {
+    _name: kw::record,
+    _paren: token::Paren,
+    target: Expr,
+}
+
+impl Parse for RecordAccess {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        Ok(Self {
+            _name: input.parse()?,
+            _paren: parenthesized!(content in input),
+            target: content.parse()?,
+        })
+    }
+}
+
+// `align(N)` or `assert_align(N)`
+struct AlignAccess {
+    kind: AlignKind,
+    _paren: token::Paren,
+    align: Expr,
+}
+
+impl Parse for AlignAccess {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        Ok(Self {
+            kind: input.parse()?,
+            _paren: parenthesized!(content in input),
+            align: content.parse()?,
+        })
+    }
+}
+
+enum AlignKind {
+    // `assert_align(N)`, which only checks the alignment.
+    Check(kw::assert_align),
+    // `align(N)`, which moves the pointer forward until it is aligned.
+    Up(kw::align),
+}
+
+impl Parse for AlignKind {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let l = input.lookahead1();
+        if l.peek(kw::align) {
+            input.parse().map(Self::Up)
+        } else if l.peek(kw::assert_align) {
+            input.parse().map(Self::Check)
+        } else {
+            Err(l.error())
+        }
+    }
+}
+
+// `with_addr(addr)` or `map_addr(|addr| ...)`
+struct AddrAccess {
+    kind: AddrKind,
+    _paren: token::Paren,
+    value: Expr,
+}
+
+impl Parse for AddrAccess {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        Ok(Self {
+            kind: input.parse()?,
+            _paren: parenthesized!(content in input),
+            value: content.parse()?,
+        })
+    }
+}
+
+enum AddrKind {
+    // `with_addr(addr)`, which replaces the address.
+    With(kw::with_addr),
+    // `map_addr(|addr| ...)`, which changes the address with a function.
+    Map(kw::map_addr),
+}
+
+impl Parse for AddrKind {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let l = input.lookahead1();
+        if l.peek(kw::with_addr) {
+            input.parse().map(Self::With)
+        } else if l.peek(kw::map_addr) {
+            input.parse().map(Self::Map)
+        } else {
+            Err(l.error())
+        }
+    }
+}
+
+// `<- value`, `<-{vol} value`, or `<-{unaligned} value`
+struct WriteAccess {
+    _arrow: Token![<-],
+    kind: Option<WriteKind>,
+    value: Expr,
+}
+
+impl Parse for WriteAccess {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let _arrow = input.parse()?;
+        // `{vol}` and `{unaligned}` are always the kind of write, even though they
+        // could also be a block expression, so that the same tokens never mean two things.
+        let kind = if WriteKind::peek_braced(input) {
+            let content;
+            braced!(content in input);
+            Some(content.parse()?)
+        } else {
+            None
+        };
+        Ok(Self {
+            _arrow,
+            kind,
+            value: input.parse()?,
         })
     }
 }
 
+enum WriteKind {
+    Volatile(kw::vol),
+    Unaligned(kw::unaligned),
+}
+
+impl WriteKind {
+    /// Returns whether the next token is braces that contain only a kind of write.
+    fn peek_braced(input: ParseStream) -> bool {
+        let check = || -> syn::Result<bool> {
+            let fork = input.fork();
+            let content;
+            braced!(content in fork);
+            content.parse::<WriteKind>()?;
+            Ok(content.is_empty())
+        };
+        input.peek(token::Brace) && check().unwrap_or(false)
+    }
+}
+
+impl Parse for WriteKind {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let l = input.lookahead1();
+        if l.peek(kw::vol) {
+            input.parse().map(Self::Volatile)
+        } else if l.peek(kw::unaligned) {
+            input.parse().map(Self::Unaligned)
+        } else {
+            Err(l.error())
+        }
+    }
+}
+
 // struct DerefAccess {
 //     dot: Token![.],
 //     star: Token![*],
@@ -296,15 +1613,35 @@ impl Parse for IndexAccess {
 
 struct OffsetAccess {
     byte: Option<kw::u8>,
+    // `~+` and `~-`, which use wrapping arithmetic.
+    wrapping: Option<Token![~]>,
     offset_type: OffsetType,
     value: OffsetValue,
 }
 
+impl OffsetAccess {
+    // a `~` on its own ends the macro, so only a `~` followed
+    // by a `+` or `-` is a wrapping offset.
+    fn peek_wrapping(input: ParseStream) -> bool {
+        input.peek(Token![~]) && (input.peek2(Token![+]) || input.peek2(Token![-]))
+    }
+}
+
 impl Parse for OffsetAccess {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        let byte = input.parse()?;
+        let wrapping: Option<Token![~]> = input.parse()?;
+        let offset_type = input.parse()?;
+        if let (Some(tilde), OffsetType::Signed(_)) = (&wrapping, &offset_type) {
+            return Err(syn::Error::new(
+                tilde.span,
+                "wrapping offsets can only use `~+` or `~-`",
+            ));
+        }
         Ok(Self {
-            byte: input.parse()?,
-            offset_type: input.parse()?,
+            byte,
+            wrapping,
+            offset_type,
             value: input.parse()?,
         })
     }
@@ -313,6 +1650,8 @@ impl Parse for OffsetAccess {
 enum OffsetType {
     Add(Token![+]),
     Sub(Token![-]),
+    // `@`, which offsets by an `isize`.
+    Signed(Token![@]),
 }
 
 impl Parse for OffsetType {
@@ -322,6 +1661,8 @@ impl Parse for OffsetType {
             input.parse().map(Self::Add)
         } else if l.peek(Token![-]) {
             input.parse().map(Self::Sub)
+        } else if l.peek(Token![@]) {
+            input.parse().map(Self::Signed)
         } else {
             Err(l.error())
         }
@@ -363,21 +1704,58 @@ impl ToTokens for OffsetValue {
 
 struct CastAccess {
     _as_token: Token![as],
+    // `as mut T` or `as const T`, which also changes the mutability.
+    mutability: Option<CastMutability>,
     ty: Type,
+    // `as T.*`, which reads right after casting.
+    read: Option<(Token![.], Token![*])>,
     // TODO: is this best syntax for this?
     arrow: Option<Token![=>]>,
 }
 
 impl Parse for CastAccess {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        let _as_token = input.parse()?;
+        let mutability = if input.peek(Token![mut]) {
+            Some(CastMutability::Mut(input.parse()?))
+        } else if input.peek(Token![const]) {
+            Some(CastMutability::Const(input.parse()?))
+        } else {
+            None
+        };
+        let ty = input.parse()?;
+        let read = if input.peek(Token![.]) && input.peek2(Token![*]) {
+            if let Some(mutability) = &mutability {
+                let span = match mutability {
+                    CastMutability::Mut(kw) => kw.span,
+                    CastMutability::Const(kw) => kw.span,
+                };
+                return Err(syn::Error::new(
+                    span,
+                    "the mutability of a pointer does not matter when it is only read from",
+                ));
+            }
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+        // the read already separates the cast from the next access.
+        let arrow = if read.is_none() { input.parse()? } else { None };
         Ok(Self {
-            _as_token: input.parse()?,
-            ty: input.parse()?,
-            arrow: input.parse()?,
+            _as_token,
+            mutability,
+            ty,
+            read,
+            arrow,
         })
     }
 }
 
+enum CastMutability {
+    Mut(Token![mut]),
+    Const(Token![const]),
+}
+
 struct GroupAccess {
     _paren: token::Paren,
     inner: AccessList,
@@ -393,6 +1771,43 @@ impl Parse for GroupAccess {
     }
 }
 
+struct LetAccess {
+    _let_token: Token![let],
+    name: Ident,
+    _eq: Token![=],
+    inner: AccessList,
+    _semi: Token![;],
+}
+
+impl Parse for LetAccess {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            _let_token: input.parse()?,
+            name: input.parse()?,
+            _eq: input.parse()?,
+            inner: AccessList::parse_until(input, |input| input.peek(Token![;]))?,
+            _semi: input.parse()?,
+        })
+    }
+}
+
 mod kw {
     syn::custom_keyword!(u8);
+    syn::custom_keyword!(uninit);
+    syn::custom_keyword!(pin);
+    syn::custom_keyword!(erased);
+    syn::custom_keyword!(prefixed_slice);
+    syn::custom_keyword!(record);
+    syn::custom_keyword!(len);
+    syn::custom_keyword!(addr);
+    syn::custom_keyword!(cell);
+    syn::custom_keyword!(md);
+    syn::custom_keyword!(aligned);
+    syn::custom_keyword!(align);
+    syn::custom_keyword!(assert_align);
+    syn::custom_keyword!(with_addr);
+    syn::custom_keyword!(map_addr);
+    syn::custom_keyword!(volatile);
+    syn::custom_keyword!(vol);
+    syn::custom_keyword!(unaligned);
 }
@@ -1,6 +1,9 @@
 #![no_std]
 extern crate core;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 /// Returns the address of an inner element without creating unneeded
 /// intermediate references.
 ///
@@ -13,41 +16,212 @@ extern crate core;
 /// * [`*const T`]
 /// * [`*mut T`]
 /// * [`NonNull<T>`]
+/// * `&T`, which is treated as a [`*const T`]
+/// * `&mut T`, which is treated as a [`*mut T`]
 ///
 /// All accesses (besides a dereference) will maintain that pointer type of the input pointer.
 /// This is especially nice with [`NonNull<T>`] because it makes everything involving it much
 /// more ergonomic.
 ///
+/// The base may also be written as `uninit slot`, where `slot` is a [`&mut MaybeUninit<T>`].
+/// This is the same as using a [`*mut T`] from [`MaybeUninit::as_mut_ptr()`], and is useful for
+/// initializing a value one element at a time. Reading any element that has not been initialized
+/// yet is still undefined behavior.
+///
+/// The base may also be written as `ref value`, where `value` is a `&T` or `&mut T`. It is turned
+/// into a [`NonNull<T>`] with [`NonNull::from()`](core::ptr::NonNull::from), so the result is a
+/// [`NonNull<T>`] instead of the [`*const T`] or [`*mut T`] that a plain reference gives, since
+/// a reference is never null. With or without `ref`, a pointer that came from a reference still
+/// follows the aliasing rules of that reference. Notably, nothing may be written through a pointer
+/// that came from a `&T` (except inside of an [`UnsafeCell`](core::cell::UnsafeCell)), even though
+/// [`NonNull<T>`] allows writes, and none of them may be used after the reference is gone.
+///
+/// The base may also be written as `pin pinned`, where `pinned` is a [`Pin<&T>`], a
+/// [`Pin<&mut T>`], or a pinned pointer from `Pointer::pin`. The result is then a pinned pointer
+/// as well, which can be turned back into a [`Pin`] with `into_pin_ref` or `into_pin_mut`, so
+/// that it does not have to be pinned again after every access. Because structural pinning is
+/// decided by each field, using `pin` is an assertion by the caller that every element that is
+/// accessed is [structurally pinned](core::pin#projections-and-structural-pinning). Since the
+/// result has to stay a pointer, it cannot end with an access that reads a value. It also cannot
+/// use `.*` anywhere, because whatever is read out of a pinned value is not pinned itself, and it
+/// cannot end with `~`, because the result is always a pinned pointer.
+///
+/// An erased pointer like `*mut c_void` can be given its real type with an `erased` base, like
+/// `element_ptr!(erased handle as Foo => .field)`. The `T` in `as T` is always the type that is
+/// pointed to, even if it is a pointer type itself, and the kind of pointer stays the same. This
+/// is the same as starting the accesses with a cast, like `element_ptr!(handle => as Foo => .field)`.
+/// Without `erased`, a cast written in the base expression (`handle as T`) is just a normal Rust cast.
+///
 /// ### Element accesses
 ///
 /// The following a table describes each of the possible accesses that can be inside the macro.
 /// These can all be chained by simply putting one after another.
 ///
-/// | Access Kind     | Syntax        |           | Equivalent Pointer Expression                  |
-/// |-----------------|---------------|-----------|------------------------------------------------|
-/// | Field           | `.field`      |           | <code>[addr_of!]\((*ptr).field)</code>         |
-/// | Index           | `[index]`     |           | <code>ptr.[cast::\<T>]\().[add]\(index)</code> |
-/// | Add Offset      | `+ count`     | [1](#sl1) | <code>ptr.[add]\(count)</code>                 |
-/// | Sub Offset      | `- count`     | [1](#sl1) | <code>ptr.[sub]\(count)</code>                 |
-/// | Byte Add Offset | `u8+ bytes`   | [1](#sl1) | <code>ptr.[byte_add]\(bytes)</code>            |
-/// | Byte Sub Offset | `u8- bytes`   | [1](#sl1) | <code>ptr.[byte_sub]\(bytes)</code>            |
-/// | Cast            | `as T =>`     | [2](#sl2) | <code>ptr.[cast::\<T>]\()</code>               |
-/// | Dereference     | `.*`          | [3](#sl3) | <code>ptr.[read]\()</code>                     |
-/// | Grouping        | `( ... )`     |           | Just groups the inner accesses for clarity.    |
+/// | Access Kind           | Syntax                   |             | Equivalent Pointer Expression                                     |
+/// |-----------------------|--------------------------|-------------|-------------------------------------------------------------------|
+/// | Field                 | `.field`                 |             | <code>[addr_of!]\((*ptr).field)</code>                            |
+/// | Typed Field           | `.field: T`              |             | Same as `.field`, but fails to compile if the field is not a `T`. |
+/// | Index                 | `[index]`                |             | <code>ptr.[cast::\<T>]\().[add]\(index)</code>                    |
+/// | Multi-Index           | `[row, col]`             |             | <code>ptr.[cast::\<T>]\().[add]\(row * C + col)</code>            |
+/// | Split                 | `[..mid \| mid..]`       | [5](#sl5)   | <code>ptr.[split_at]\(mid)</code>                                 |
+/// | Subslice              | `[start..end]`           | [13](#sl13) | A slice pointer to the elements in `start..end`.                  |
+/// | Length                | `.len()`                 | [14](#sl14) | <code>ptr.[len]\()</code>                                         |
+/// | Address               | `.addr()`                | [18](#sl18) | <code>ptr.[addr]\()</code>                                        |
+/// | Cell Contents         | `.{cell}`                | [15](#sl15) | <code>[UnsafeCell::raw_get]\(ptr)</code>                          |
+/// | Uninit Contents       | `.{uninit}`              | [16](#sl16) | <code>ptr.[cast::\<T>]\()</code>                                  |
+/// | ManuallyDrop Contents | `.{md}`                  |             | <code>ptr.[cast::\<T>]\()</code>                                  |
+/// | Aligned Contents      | `.{aligned}`             | [21](#sl21) | Same as `.0`, after checking the alignment of the wrapper.        |
+/// | Prefixed Slice        | `.prefixed_slice::<L>()` | [6](#sl6)   | A slice of the data that follows a length of type `L`.            |
+/// | Add Offset            | `+ count`                | [1](#sl1)   | <code>ptr.[add]\(count)</code>                                    |
+/// | Sub Offset            | `- count`                | [1](#sl1)   | <code>ptr.[sub]\(count)</code>                                    |
+/// | Byte Add Offset       | `u8+ bytes`              | [1](#sl1)   | <code>ptr.[byte_add]\(bytes)</code>                               |
+/// | Byte Sub Offset       | `u8- bytes`              | [1](#sl1)   | <code>ptr.[byte_sub]\(bytes)</code>                               |
+/// | Signed Offset         | `@ count`                | [1](#sl1)   | <code>ptr.[offset]\(count)</code>                                 |
+/// | Byte Signed Offset    | `u8@ bytes`              | [1](#sl1)   | <code>ptr.[byte_offset]\(bytes)</code>                            |
+/// | Wrapping Add Offset   | `~+ count`               | [10](#sl10) | <code>ptr.[wrapping_add]\(count)</code>                           |
+/// | Wrapping Sub Offset   | `~- count`               | [10](#sl10) | <code>ptr.[wrapping_sub]\(count)</code>                           |
+/// | Wrapping Byte Add     | `u8~+ bytes`             | [10](#sl10) | <code>ptr.[wrapping_byte_add]\(bytes)</code>                      |
+/// | Wrapping Byte Sub     | `u8~- bytes`             | [10](#sl10) | <code>ptr.[wrapping_byte_sub]\(bytes)</code>                      |
+/// | Assert Align          | `assert_align(N)`        | [8](#sl8)   | Checks that the pointer is aligned to `N` bytes.                  |
+/// | Align                 | `align(N)`               | [11](#sl11) | <code>ptr.[add]\(ptr.[align_offset]\(N))</code>                   |
+/// | With Address          | `with_addr(addr)`        | [19](#sl19) | <code>ptr.[with_addr]\(addr)</code>                               |
+/// | Map Address           | `map_addr(f)`            | [19](#sl19) | <code>ptr.[map_addr]\(f)</code>                                   |
+/// | Cast                  | `as T =>`                | [2](#sl2)   | <code>ptr.[cast::\<T>]\()</code>                                  |
+/// | Mutable Cast          | `as mut T =>`            | [17](#sl17) | <code>ptr.[cast_mut]\().[cast::\<T>]\()</code>                    |
+/// | Const Cast            | `as const T =>`          | [17](#sl17) | <code>ptr.[cast_const]\().[cast::\<T>]\()</code>                  |
+/// | Dereference           | `.*`                     | [3](#sl3)   | <code>ptr.[read]\()</code>                                        |
+/// | Volatile Dereference  | `.*volatile`             | [3](#sl3)   | <code>ptr.[read_volatile]\()</code>                               |
+/// | Unaligned Dereference | `.*unaligned`            | [3](#sl3)   | <code>ptr.[read_unaligned]\()</code>                              |
+/// | Cast and Read         | `as T.*`                 | [3](#sl3)   | <code>ptr.[cast::\<T>]\().[read]\()</code>                        |
+/// | Null Check            | `.?`                     | [20](#sl20) | Returns `None` if <code>ptr.[is_null]\()</code>.                  |
+/// | Reference             | `.&?`                    | [12](#sl12) | <code>ptr.[as_ref]\()</code>                                      |
+/// | Mutable Reference     | `.&mut?`                 | [12](#sl12) | <code>ptr.[as_mut]\()</code>                                      |
+/// | Write                 | `<- value`               | [9](#sl9)   | <code>ptr.[write]\(value)</code>                                  |
+/// | Volatile Write        | `<-{vol} value`          | [9](#sl9)   | <code>ptr.[write_volatile]\(value)</code>                         |
+/// | Unaligned Write       | `<-{unaligned} value`    | [9](#sl9)   | <code>ptr.[write_unaligned]\(value)</code>                        |
+/// | Grouping              | `( ... )`                |             | Just groups the inner accesses for clarity.                       |
+/// | Binding               | `let name = ... ;`       | [4](#sl4)   | Binds the result of the inner accesses to `name`.                 |
+/// | Record                | `record(&mut offset)`    | [7](#sl7)   | Writes the byte offset from the base pointer into `offset`.       |
 ///
 /// 1. <span id="sl1"> `count`/`bytes` may either be an integer literal
-///     or an expression wrapped in parentheses. </span>
+///    or an expression wrapped in parentheses. For `@` they are an `isize`, so
+///    a negative offset must be in parentheses, like `@ (-2)`. </span>
 /// 2. <span id="sl2"> The `=>` may be omitted if the cast is the
-///     last access in a group. </span>
+///    last access in a group. </span>
 /// 3. <span id="sl3"> A dereference may return a value that is not a pointer only
-///     if it is the final access in the macro. In general it is encouraged to not
-///     do this and only use deferencing for inner pointers. </span>
+///    if it is the final access in the macro. In general it is encouraged to not
+///    do this and only use deferencing for inner pointers. </span>
+/// 4. <span id="sl4"> The inner accesses start from the current pointer, but do not
+///    move it. `name` may be used in any expression later in the macro, such as
+///    an index. </span>
+/// 5. <span id="sl5"> Both sides must use the same `mid` expression, which is only evaluated once.
+///    This can only be used on slice pointers, and must be the final access because it returns
+///    a tuple of two slice pointers. </span>
+/// 6. <span id="sl6"> The length is read from the current pointer, and the data starts right after it,
+///    rounded up to the alignment of the element type. The element type is usually inferred, but it
+///    can also be given with `.prefixed_slice::<L, E>()`. </span>
+/// 7. <span id="sl7"> `offset` may be any expression that evaluates to a `&mut usize`. This does not move
+///    the pointer, so it can be used partway through the accesses. It is mostly useful for debugging
+///    and code generation, like collecting the offsets of several fields at once. </span>
+/// 8. <span id="sl8"> The check is a `debug_assert!`, so it is only done when debug assertions are enabled.
+///    The pointer is not changed. This is useful for alignment that the type system doesn't know about,
+///    such as in the middle of a byte buffer. </span>
+/// 9. <span id="sl9"> This must be the final access, and makes the macro return `()`. The old value is
+///    overwritten without being dropped. Writing through a `*const T` fails to compile. A `{vol}` or
+///    `{unaligned}` right after the `<-` is always the kind of write, and never a block expression. </span>
+/// 10. <span id="sl10"> The pointer may temporarily go out of bounds, which is useful for things like
+///     ring buffers. It must be back in bounds before it is dereferenced. `count`/`bytes` follow the
+///     same rules as in [1](#sl1). </span>
+/// 11. <span id="sl11"> `N` must be a power of two. The pointer moves forward to the next address that is
+///     aligned to `N`, which may be outside of the current field. If it can't be aligned, the pointer
+///     is not changed. </span>
+/// 12. <span id="sl12"> This must be the final access. For a raw pointer it returns an `Option<&T>`, which is
+///     `None` if the pointer is null, and for a [`NonNull<T>`] it returns a `&T`. The lifetime of the
+///     reference is unbounded, so it must not be used after the data is gone. `.&mut?` is the same,
+///     but gives a mutable reference and fails to compile on a `*const T`. </span>
+/// 13. <span id="sl13"> Any kind of range works, like `start..=end` or `start..`. A range without an end
+///     uses the length of the array or slice being pointed to. The range is not checked against that
+///     length, so it must stay in bounds like an index does. </span>
+/// 14. <span id="sl14"> This must be the final access, and makes the macro return the number of elements
+///     as a `usize`. For a slice this comes from the pointer itself, and for an array `[T; N]` it is
+///     just `N`, so no memory is read. A field named `len` is still accessed with `.len`. </span>
+/// 15. <span id="sl15"> This works on both an [`UnsafeCell<T>`] and a [`Cell<T>`], and never reads
+///     memory or creates a reference. The pointer keeps its mutability, so writing to the contents
+///     through a `*const` pointer still fails to compile. </span>
+/// 16. <span id="sl16"> This goes from a [`MaybeUninit<T>`] to the `T` inside of it, which has the same
+///     layout. It does not need the value to be initialized, but reading it still does. </span>
+/// 17. <span id="sl17"> This also changes the result to a `*mut T` or a `*const T`, no matter what the
+///     base pointer was. The `=>` may be omitted in the same way as [2](#sl2). Going to a `*mut T` only
+///     lets writes compile, and does not make them allowed. </span>
+/// 18. <span id="sl18"> This must be the final access, and makes the macro return the address as a `usize`.
+///     It uses [`pointer::addr()`][addr], so the provenance of the pointer is not exposed, which is
+///     useful for logging under the strict provenance lints. A field named `addr` is still accessed
+///     with `.addr`. </span>
+/// 19. <span id="sl19"> `addr` may be any expression that evaluates to a `usize`, and `f` any function
+///     or closure from a `usize` to a `usize`. The provenance of the pointer is kept, so the new address
+///     must still be inside of the allocated object that the pointer came from before it is used, and
+///     must never be `0` for a [`NonNull<T>`]. This is useful for tagged pointers. </span>
+/// 20. <span id="sl20"> If this is used anywhere, the macro returns an `Option` of what it would have
+///     returned otherwise, which is `None` if the pointer was null at any of the checks. The rest of the
+///     accesses are skipped after a null pointer, so they can rely on it not being null, such as when
+///     following the `next` pointers of a linked list. This also works in `element_projector!`. </span>
+/// 21. <span id="sl21"> This goes into the only field of a `#[repr(align(N))]` wrapper, like
+///     `struct CacheLine<T>(T)`, and checks that the wrapper is aligned to `N` in the same way as
+///     [8](#sl8). These wrappers can't be `#[repr(transparent)]`, so this is a field access of `.0`
+///     and not a cast. </span>
+///
+/// Ending the accesses with a `~` makes the macro return the [`helper::Pointer<M, T>`](helper::Pointer)
+/// it uses internally instead of the raw pointer, where `M` is `Const`, `Mut`, or `NonNull` depending
+/// on the base pointer. This is useful for continuing to move the pointer around with its methods,
+/// or for storing it while keeping track of its mutability.
 ///
 /// # Safety
 /// * All of the [requirements][offsetreq] for [`offset()`] must be upheld. This is relevant for every
-///     access except for dereferencing, grouping, and casting.
-/// * The derefence access (`.*`) unconditionally reads from the pointer, and must not violate
-///     any [requirements][readreq] related to that.
+///   access except for dereferencing, grouping, casting, the wrapping offsets, and changing
+///   the address.
+/// * The wrapping offsets (`~+`, `~-`, `u8~+`, and `u8~-`) may leave the allocated object, but a
+///   [`NonNull<T>`] must never wrap around to null.
+/// * The address accesses (`with_addr(addr)` and `map_addr(f)`) may make any address, but the result
+///   must be inside of the allocated object that the pointer came from before it is used again.
+///   A [`NonNull<T>`] must also never be given the null address.
+/// * A mutable cast (`as mut T`) is an assertion that the pointee may be written through the result.
+///   Writing through a pointer that came from a `*const T` or a `&T` is only allowed if the pointee
+///   was originally mutable, or is inside of an [`UnsafeCell`](core::cell::UnsafeCell).
+/// * The derefence accesses (`.*`, `.*volatile`, `.*unaligned`, and `as T.*`) unconditionally read
+///   from the pointer, and must not violate any [requirements][readreq] related to that. The same
+///   goes for reading the length in the prefixed slice access. A volatile read has the same alignment
+///   and validity requirements as a normal one, while an unaligned read only drops the alignment
+///   requirement.
+/// * The write accesses (`<- value`, `<-{vol} value`, and `<-{unaligned} value`) unconditionally write
+///   to the pointer, and must not violate any [requirements][writereq] related to that, besides
+///   alignment for an unaligned write.
+/// * The reference access (`.&?`) creates a shared reference, so the pointee must be aligned,
+///   initialized, and a valid `T`. It must also not be mutated while the reference is alive,
+///   except inside of an [`UnsafeCell`](core::cell::UnsafeCell).
+/// * The mutable reference access (`.&mut?`) creates a mutable reference, which has the same
+///   requirements, but is also exclusive. While it is alive, the pointee must not be read or
+///   written through any other pointer or reference, including ones to the containing value.
+///
+/// With the `debug-checks` feature, offsets and indices that could never stay inside of an
+/// allocated object panic when debug assertions are enabled. The real bounds of the allocated
+/// object are not known, so this only catches obviously wrong jumps, like a negative index that
+/// was cast to a `usize`. None of this is done in release builds.
+///
+#[cfg_attr(
+    all(feature = "debug-checks", debug_assertions),
+    doc = "```should_panic"
+)]
+#[cfg_attr(
+    not(all(feature = "debug-checks", debug_assertions)),
+    doc = "```ignore"
+)]
+/// use element_ptr::element_ptr;
+///
+/// let values = [1u32, 2, 3];
+/// let index: isize = -1;
+///
+/// unsafe { element_ptr!(&values => [index as usize]) };
+/// ```
 ///
 /// # Examples
 ///
@@ -145,228 +319,5085 @@ extern crate core;
 /// }
 /// ```
 ///
-// the following links need to be explicitly put because rustdoc cannot refer to pointer methods.
-/// [addr_of!]: core::ptr::addr_of!
-/// [read]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read
-/// [add]: https://doc.rust-lang.org/core/primitive.pointer.html#method.add
-/// [sub]: https://doc.rust-lang.org/core/primitive.pointer.html#method.sub
-/// [byte_add]: https://doc.rust-lang.org/core/primitive.pointer.html#method.byte_add
-/// [byte_sub]: https://doc.rust-lang.org/core/primitive.pointer.html#method.byte_sub
-/// [`offset()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.offset
-/// [offsetreq]: https://doc.rust-lang.org/core/primitive.pointer.html#safety-2
-/// [readreq]: https://doc.rust-lang.org/core/ptr/fn.read.html#safety
-/// [cast::\<T>]: https://doc.rust-lang.org/core/primitive.pointer.html#method.cast
-/// [`*const T`]: https://doc.rust-lang.org/core/primitive.pointer.html
-/// [`*mut T`]: https://doc.rust-lang.org/core/primitive.pointer.html
-/// [`NonNull<T>`]: core::ptr::NonNull
-// #[cfg(not(doctest))] // just don't doctest any of these. Macros are way too hard to do.
-pub use element_ptr_macro::element_ptr;
-
-#[doc(hidden)]
-pub mod helper {
-    use core::{marker::PhantomData, mem::ManuallyDrop};
-    /// A trait that describes the mutability of a pointer.
-    ///
-    /// # Safety
-    /// * The types that implement this trait should act as a "brand" on
-    ///     the pointer type, uniquely identifying it.
-    /// * `Var<T>` should have the same variance as `Raw<T>`.
-    pub unsafe trait Mutability {
-        type Var<T: ?Sized>;
-        type Raw<T: ?Sized>: IsPtr<M = Self, T = T>;
-    }
-    /// A trait that describes a pointer.
-    ///
-    /// # Safety
-    /// * This should only be implemented on a pointer type. This type must be allowed to be
-    ///     transmuted to a `*const T` and read from.
-    pub unsafe trait IsPtr: Copy {
-        type M: Mutability;
-        type T: ?Sized;
-    }
-
-    pub enum Const {}
-    pub enum Mut {}
-    // NonNull is safe here because all of the methods on `Pointer`
-    // and related freestanding functions all require the pointer
-    // to stay within the bounds of the allocated object.
-    // Because the null address is not ever part of an allocated object,
-    // this means that as long as a pointer is created from an existing `NonNull`,
-    // all uses that would invalidate the `NonNull` would be UB regardless.
-    pub enum NonNull {}
-
-    unsafe impl Mutability for Const {
-        type Var<T: ?Sized> = fn() -> T;
-        type Raw<T: ?Sized> = *const T;
-    }
-    unsafe impl Mutability for Mut {
-        type Var<T: ?Sized> = fn(T) -> T;
-        type Raw<T: ?Sized> = *mut T;
-    }
-    unsafe impl Mutability for NonNull {
-        type Var<T: ?Sized> = fn() -> T;
-        type Raw<T: ?Sized> = core::ptr::NonNull<T>;
-    }
-
-    unsafe impl<T: ?Sized> IsPtr for *mut T {
-        type M = Mut;
-        type T = T;
-    }
-    unsafe impl<T: ?Sized> IsPtr for *const T {
-        type M = Const;
-        type T = T;
-    }
-    unsafe impl<T: ?Sized> IsPtr for core::ptr::NonNull<T> {
-        type M = NonNull;
-        type T = T;
-    }
-
-    // Store a const pointer to do the manipulations with.
-    #[repr(transparent)]
-    pub struct Pointer<M: Mutability, T: ?Sized>(*const T, PhantomData<(M, M::Var<T>)>);
-
-    impl<M: Mutability, T: ?Sized> Clone for Pointer<M, T> {
-        fn clone(&self) -> Self {
-            *self
-        }
-    }
-    impl<M: Mutability, T: ?Sized> Copy for Pointer<M, T> {}
-
-    #[inline(always)]
-    pub const fn new_pointer<P: IsPtr>(ptr: P) -> Pointer<P::M, P::T> {
-        // Safety
-        // `IsPtr` guarantees that `P` may be transmuted into `*const P::T`.
-        unsafe { Pointer(transmute_unchecked::<P, *const P::T>(ptr), PhantomData) }
-    }
-
-    impl<M: Mutability, T: ?Sized> Pointer<M, T> {
-        /// Copies the address and type of a pointer to this pointer, keeping
-        /// mutability intact.
-        ///
-        /// # Safety
-        /// * `ptr` must be within the same allocated object as `self`.
-        #[inline(always)]
-        pub const unsafe fn copy_addr<E: ?Sized>(self, ptr: *const E) -> Pointer<M, E> {
-            Pointer(ptr, PhantomData)
-        }
-        /// Returns the inner pointer type.
-        #[inline(always)]
-        pub const fn into_inner(self) -> M::Raw<T> {
-            // Safety
-            // `Pointer<M, T>` can only be created with from a `P: IsPtr`,
-            // an `IsPtr` guarantees that `*const T` may be cast to `M::Raw<T>`.
-            unsafe { transmute_unchecked(self.0) }
-        }
-        /// Returns a `*const T` that points to the same place as this pointer.
-        #[inline(always)]
-        pub const fn into_const(self) -> *const T {
-            self.0
-        }
-        /// Casts this pointer to another type.
-        #[inline(always)]
-        pub const fn cast<U>(self) -> Pointer<M, U> {
-            Pointer(self.0.cast(), PhantomData)
-        }
-    }
-
-    impl<M: Mutability, T> Pointer<M, T> {
-        /// Calculates the offset of this pointer in units of `T`.
-        ///
-        /// This function is a wrapper around [`pointer::add()`].
-        /// See its documentation for more info including the safety requirements.
-        ///
-        /// [`pointer::add()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.add
-        #[inline(always)]
-        pub const unsafe fn add(mut self, count: usize) -> Self {
-            self.0 = self.0.add(count);
-            self
-        }
-        /// Calculates the offset of this pointer in units of `T`.
-        ///
-        /// This function is a wrapper around [`pointer::sub()`].
-        /// See its documentation for more info including the safety requirements.
-        ///
-        /// [`pointer::sub()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.sub
-        #[inline(always)]
-        pub const unsafe fn sub(mut self, count: usize) -> Self {
-            self.0 = self.0.sub(count);
-            self
-        }
-        /// Calculates the offset of this pointer in units of `T`.
-        ///
-        /// This function is a wrapper around [`pointer::offset()`].
-        /// See its documentation for more info including the safety requirements.
-        ///
-        /// [`pointer::offset()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.offset
-        #[inline(always)]
-        pub const unsafe fn offset(mut self, count: isize) -> Self {
-            self.0 = self.0.offset(count);
-            self
-        }
-        /// Calculates the offset of this pointer in bytes.
-        ///
-        /// This function is a wrapper around [`pointer::byte_add()`].
-        /// See its documentation for more info including the safety requirements.
-        ///
-        /// [`pointer::byte_add()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.byte_add
-        #[inline(always)]
-        pub const unsafe fn byte_add(mut self, count: usize) -> Self {
-            self.0 = self.0.byte_add(count);
-            self
-        }
-        /// Calculates the offset of this pointer in bytes.
-        ///
-        /// This function is a wrapper around [`pointer::byte_sub()`].
-        /// See its documentation for more info including the safety requirements.
-        ///
-        /// [`pointer::byte_sub()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.byte_sub
-        #[inline(always)]
-        pub const unsafe fn byte_sub(mut self, count: usize) -> Self {
-            self.0 = self.0.byte_sub(count);
-            self
-        }
-        /// Calculates the offset of this pointer in bytes.
-        ///
-        /// This function is a wrapper around [`pointer::byte_offset()`].
-        /// See its documentation for more info including the safety requirements.
-        ///
-        /// [`pointer::byte_offset()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.byte_offset
-        #[inline(always)]
-        pub const unsafe fn byte_offset(mut self, count: isize) -> Self {
-            self.0 = self.0.byte_offset(count);
-            self
-        }
-        /// Reads the value from behind this pointer.
-        ///
-        /// This function is a wrapper around [`pointer::read()`].
-        /// See its documentation for more info including the safety requirements.
-        ///
-        /// [`pointer::read()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read
-        #[inline(always)]
-        pub const unsafe fn read(self) -> T {
-            self.0.read()
-        }
-    }
-
-    // This is a freestanding function to make the error message
-    // when T doesn't implement `CanIndex` slightly better.
-    #[inline(always)]
-    pub const unsafe fn index<M: Mutability, T: ?Sized>(
-        ptr: Pointer<M, T>,
-        index: usize,
-    ) -> Pointer<M, T::E>
-    where
-        T: CanIndex,
-    {
-        let base = ptr.into_const().cast::<T::E>();
-        let ptr = base.add(index);
-        Pointer(ptr, PhantomData)
-    }
-
-    /// Transmutes from `F` to `T`. All of the normal safety requirements
-    /// for transmutations hold here.
-    ///
-    /// This is just `transmute_copy` except by value.
+/// A value can be initialized one field at a time through a [`MaybeUninit`](core::mem::MaybeUninit).
+///
+/// ```
+/// use element_ptr::element_ptr;
+/// use std::mem::MaybeUninit;
+///
+/// struct Point {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// let mut slot = MaybeUninit::<Point>::uninit();
+///
+/// let point = unsafe {
+///     element_ptr!(uninit &mut slot => .x).write(1.0);
+///     element_ptr!(uninit &mut slot => .y).write(2.0);
+///     slot.assume_init()
+/// };
+///
+/// assert_eq!((point.x, point.y), (1.0, 2.0));
+/// ```
+///
+/// A [`MaybeUninit`](core::mem::MaybeUninit) that is itself an element can be gone
+/// into with `.{uninit}`.
+///
+/// ```
+/// use element_ptr::element_ptr;
+/// use std::mem::MaybeUninit;
+///
+/// struct Header {
+///     id: u32,
+///     flags: u16,
+/// }
+///
+/// struct Packet {
+///     header: MaybeUninit<Header>,
+///     body: [u8; 4],
+/// }
+///
+/// let mut packet = Packet { header: MaybeUninit::uninit(), body: [1, 2, 3, 4] };
+/// let ptr = &mut packet as *mut Packet;
+///
+/// let header = unsafe {
+///     element_ptr!(ptr => .header.{uninit}.id <- 7);
+///     element_ptr!(ptr => .header.{uninit}.flags <- 0b10);
+///     packet.header.assume_init()
+/// };
+///
+/// assert_eq!((header.id, header.flags), (7, 0b10));
+/// ```
+///
+/// The same goes for a [`ManuallyDrop`](core::mem::ManuallyDrop) with `.{md}`, which is useful
+/// for dropping the value inside of it in place.
+///
+/// ```
+/// use element_ptr::element_ptr;
+/// use std::{mem::ManuallyDrop, ptr};
+///
+/// struct Entry {
+///     key: u64,
+///     value: ManuallyDrop<String>,
+/// }
+///
+/// let mut entry = Entry { key: 1, value: ManuallyDrop::new("cached".to_string()) };
+/// let ptr = &mut entry as *mut Entry;
+///
+/// let value: *mut String = unsafe { element_ptr!(ptr => .value.{md}) };
+/// assert_eq!(value, ptr::addr_of_mut!(entry.value).cast::<String>());
+///
+/// unsafe { ptr::drop_in_place(value) };
+/// ```
+///
+/// Arrays with a const generic length work the same way, and the length is still known
+/// when indexing or turning them into a slice.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// struct Ring<const N: usize> {
+///     head: usize,
+///     buf: [u8; N],
+/// }
+///
+/// fn newest<const N: usize>(ring: &Ring<N>) -> u8 {
+///     let ptr = ring as *const Ring<N>;
+///     unsafe { element_ptr!(ptr => let head = .head.*; .buf[(head + N - 1) % N].*) }
+/// }
+///
+/// fn contents<const N: usize>(ring: &Ring<N>) -> *const [u8] {
+///     let ptr = ring as *const Ring<N>;
+///     let buf: *const [u8] = unsafe { element_ptr!(ptr => .buf[..]) };
+///     assert_eq!(buf.len(), N);
+///     assert_eq!(unsafe { element_ptr!(ptr => .buf.len()) }, N);
+///     buf
+/// }
+///
+/// let ring = Ring { head: 1, buf: [5, 6, 7, 8] };
+/// assert_eq!(newest(&ring), 5);
+/// assert_eq!(unsafe { &*contents(&ring) }, &[5, 6, 7, 8]);
+///
+/// let ring = Ring { head: 0, buf: [1; 16] };
+/// assert_eq!(contents(&ring).len(), 16);
+/// ```
+///
+/// The field access is done with [`addr_of!`](core::ptr::addr_of) on the pointer itself, so accessing
+/// the unsized last field of a custom dynamically sized type keeps the length or vtable that the
+/// pointer carries, and the result is a wide pointer as well.
+///
+/// ```
+/// use element_ptr::element_ptr;
+/// use std::fmt::Debug;
+///
+/// struct Packet<T: ?Sized> {
+///     kind: u8,
+///     data: T,
+/// }
+///
+/// let packet: Box<Packet<[u16]>> = Box::new(Packet { kind: 2, data: [10, 20, 30] });
+/// let ptr = &*packet as *const Packet<[u16]>;
+///
+/// let data: *const [u16] = unsafe { element_ptr!(ptr => .data) };
+/// assert_eq!(data.len(), 3);
+/// assert_eq!(unsafe { element_ptr!(ptr => .data[2].*) }, 30);
+///
+/// let packet: Box<Packet<dyn Debug>> = Box::new(Packet { kind: 1, data: 'x' });
+/// let ptr = &*packet as *const Packet<dyn Debug>;
+///
+/// let data: *const dyn Debug = unsafe { element_ptr!(ptr => .data) };
+/// assert_eq!(format!("{:?}", unsafe { &*data }), "'x'");
+/// ```
+///
+/// An index into an array of structs points to a single element, so it can be followed by
+/// a field of that element.
+///
+/// ```
+/// use element_ptr::element_ptr;
+/// use std::ptr::{addr_of, NonNull};
+///
+/// struct Item {
+///     id: u32,
+///     name: &'static str,
+/// }
+///
+/// struct Inventory {
+///     count: usize,
+///     items: [Item; 3],
+/// }
+///
+/// let mut inventory = Inventory {
+///     count: 3,
+///     items: [
+///         Item { id: 1, name: "sword" },
+///         Item { id: 2, name: "shield" },
+///         Item { id: 3, name: "potion" },
+///     ],
+/// };
+///
+/// for i in 0..inventory.count {
+///     let ptr = &inventory as *const Inventory;
+///     let name: *const &'static str = unsafe { element_ptr!(ptr => .items[i].name) };
+///     assert_eq!(name, addr_of!(inventory.items[i].name));
+/// }
+///
+/// let ptr = NonNull::from(&mut inventory);
+/// let name: NonNull<&'static str> = unsafe { element_ptr!(ptr => .items[1].name) };
+/// unsafe { name.write("buckler") };
+///
+/// let id = unsafe { element_ptr!(ptr => .items[2].id.*) };
+/// assert_eq!(id, 3);
+/// assert_eq!(inventory.items[1].name, "buckler");
+/// ```
+///
+/// A binding can read a value partway through the accesses, such as the length
+/// of some length-prefixed data, and use it later on.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// struct Prefixed {
+///     len: usize,
+///     data: [u32; 8],
+/// }
+///
+/// let prefixed = Prefixed { len: 3, data: [1, 2, 3, 4, 5, 6, 7, 8] };
+/// let ptr = &prefixed as *const Prefixed;
+///
+/// let last = unsafe { element_ptr!(ptr => let len = .len.*; .data[len - 1]) };
+///
+/// assert_eq!(unsafe { *last }, 3);
+/// ```
+///
+/// Length-prefixed data can also be turned directly into a slice pointer.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// #[repr(C, align(2))]
+/// struct Message([u8; 8]);
+///
+/// // a `u16` length, followed by that many bytes.
+/// let mut message = Message([0; 8]);
+/// message.0[..2].copy_from_slice(&5u16.to_ne_bytes());
+/// message.0[2..7].copy_from_slice(b"hello");
+///
+/// let ptr = &message as *const Message;
+/// let data: *const [u8] = unsafe { element_ptr!(ptr => .0.prefixed_slice::<u16>()) };
+///
+/// assert_eq!(unsafe { &*data }, b"hello");
+///
+/// let last = unsafe { element_ptr!(ptr => .0.prefixed_slice::<u16, u8>()[4]) };
+///
+/// assert_eq!(unsafe { *last }, b'o');
+/// ```
+///
+/// The offsets of elements along the way can be collected with `record`.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// #[repr(C)]
+/// struct Header {
+///     magic: u32,
+///     sizes: [u16; 4],
+///     flags: u8,
+/// }
+///
+/// let header = Header { magic: 0, sizes: [0; 4], flags: 0 };
+/// let ptr = &header as *const Header;
+///
+/// let (mut sizes, mut third, mut flags) = (0, 0, 0);
+/// let flags_ptr = unsafe {
+///     element_ptr!(ptr =>
+///         let third_ptr = .sizes record(&mut sizes) [2] record(&mut third);
+///         .flags record(&mut flags)
+///     )
+/// };
+///
+/// assert_eq!(sizes, 4);
+/// assert_eq!(third, 8);
+/// assert_eq!(flags, 12);
+/// assert_eq!(flags_ptr, &header.flags as *const u8);
+/// ```
+///
+/// A cast followed by a dereference can be written as a single `as T.*`, which is handy for
+/// reading a value as a different type.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// struct Sample {
+///     gain: f32,
+///     channels: [u16; 2],
+/// }
+///
+/// let sample = Sample { gain: -2.0, channels: [0x1234, 0x5678] };
+/// let ptr = &sample as *const Sample;
+///
+/// let gain_bits = unsafe { element_ptr!(ptr => .gain as u32.*) };
+/// let channels = unsafe { element_ptr!(ptr => .channels as [u8; 4].*) };
+///
+/// assert_eq!(gain_bits, (-2.0f32).to_bits());
+/// assert_eq!(channels, unsafe { core::mem::transmute::<[u16; 2], [u8; 4]>(sample.channels) });
+/// ```
+///
+/// After a cast, field accesses refer to the fields of the new type, so one layout can be
+/// viewed through another.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// #[repr(C)]
+/// struct Message {
+///     tag: u32,
+///     payload: [u32; 3],
+/// }
+///
+/// #[repr(C)]
+/// struct Resize {
+///     tag: u32,
+///     width: u32,
+///     height: u32,
+///     depth: u32,
+/// }
+///
+/// #[repr(C)]
+/// struct Extent {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// let mut message = Message { tag: 7, payload: [640, 480, 1] };
+/// let ptr = &mut message as *mut Message;
+///
+/// unsafe {
+///     assert_eq!(element_ptr!(ptr => as Resize => .height.*), 480);
+///     assert_eq!(element_ptr!(ptr => .payload as Extent => .y.*), 480);
+///     // a field of the cast type can be followed by more accesses and casts.
+///     assert_eq!(element_ptr!(ptr => as Resize => .width as Extent => .y.*), 480);
+///     element_ptr!(ptr => as Resize => .depth <- 2);
+/// }
+///
+/// assert_eq!(message.payload, [640, 480, 2]);
+/// ```
+///
+/// A cast can also change whether the result is a `*mut T` or a `*const T`, such as after exclusive
+/// access to a value has been checked for.
+///
+/// ```
+/// use element_ptr::element_ptr;
+/// use std::cell::UnsafeCell;
+///
+/// struct Lock {
+///     locked: bool,
+///     value: UnsafeCell<u32>,
+/// }
+///
+/// let lock = Lock { locked: true, value: UnsafeCell::new(1) };
+/// let ptr = &lock as *const Lock;
+///
+/// unsafe {
+///     if element_ptr!(ptr => .locked.*) {
+///         // Safety: the value is inside of an `UnsafeCell`, and the lock is held.
+///         let value: *mut u32 = element_ptr!(ptr => .value as mut u32);
+///         value.write(2);
+///     }
+/// }
+///
+/// assert_eq!(unsafe { *lock.value.get() }, 2);
+///
+/// // handing out a pointer that should not be written through.
+/// let mut bytes = [0u8; 4];
+/// let read_only: *const [u8; 2] = unsafe { element_ptr!(&mut bytes => [2] as const [u8; 2]) };
+/// assert_eq!(read_only.cast::<u8>(), bytes[2..].as_ptr());
+/// ```
+///
+/// Alignment that the type system doesn't know about can be checked with `assert_align(N)`.
+/// The contents of a `#[repr(align(N))]` wrapper can be reached with `.{aligned}`, which
+/// checks the alignment of the wrapper on the way in.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// #[repr(align(64))]
+/// struct CacheLine<T>(T);
+///
+/// struct Counters {
+///     hits: CacheLine<u64>,
+///     misses: CacheLine<u64>,
+/// }
+///
+/// let counters = Counters { hits: CacheLine(3), misses: CacheLine(1) };
+/// let ptr = &counters as *const Counters;
+///
+/// let hits = unsafe { element_ptr!(ptr => .hits.0 assert_align(64)) };
+/// let misses = unsafe { element_ptr!(ptr => .misses.{aligned}) };
+///
+/// assert_eq!(hits as usize % 64, 0);
+/// assert_eq!(misses as usize % 64, 0);
+/// assert_eq!(unsafe { (*hits, *misses) }, (3, 1));
+/// ```
+///
+/// A pointer can also be moved forward to an aligned address with `align(N)`, such as
+/// before copying in bulk.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// #[repr(C, align(16))]
+/// struct Scratch {
+///     used: u8,
+///     bytes: [u8; 63],
+/// }
+///
+/// let scratch = Scratch { used: 1, bytes: [0; 63] };
+/// let ptr = &scratch as *const Scratch;
+///
+/// let aligned = unsafe { element_ptr!(ptr => .bytes [0] align(16)) };
+///
+/// assert_eq!(aligned as usize % 16, 0);
+/// assert_eq!(aligned, unsafe { ptr.cast::<u8>().add(16) });
+/// ```
+///
+/// The contents of an [`UnsafeCell`](core::cell::UnsafeCell) or [`Cell`](core::cell::Cell)
+/// can be reached with `.{cell}`, without creating a reference to the cell.
+///
+/// ```
+/// use element_ptr::element_ptr;
+/// use std::cell::{Cell, UnsafeCell};
+///
+/// struct Slot {
+///     value: UnsafeCell<u64>,
+///     version: Cell<u32>,
+/// }
+///
+/// let slot = Slot { value: UnsafeCell::new(1), version: Cell::new(0) };
+///
+/// // a `NonNull` from a `&Slot` may still write inside of its cells.
+/// unsafe {
+///     element_ptr!(ref &slot => .value.{cell} <- 2);
+///     element_ptr!(ref &slot => .version.{cell} <- 1);
+/// }
+///
+/// let value: *const u64 = unsafe { element_ptr!(&slot => .value.{cell}) };
+/// assert_eq!(value, slot.value.get().cast_const());
+/// assert_eq!((unsafe { *value }, slot.version.get()), (2, 1));
+/// ```
+///
+/// The address of a pointer can be changed without losing its provenance with `with_addr(addr)`
+/// or `map_addr(f)`, such as to clear the tag bits of a tagged pointer before following it.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// struct Node {
+///     value: u32,
+/// }
+///
+/// struct Entry {
+///     // the lowest bit marks the entry as deleted.
+///     tagged: *const Node,
+/// }
+///
+/// let node = Node { value: 7 };
+/// let entry = Entry { tagged: (&node as *const Node).map_addr(|addr| addr | 1) };
+/// let ptr = &entry as *const Entry;
+///
+/// let value = unsafe { element_ptr!(ptr => .tagged.* map_addr(|addr| addr & !1) .value.*) };
+/// assert_eq!(value, 7);
+///
+/// let untagged = (&node as *const Node).addr();
+/// let node_ptr = unsafe { element_ptr!(ptr => .tagged.* with_addr(untagged)) };
+/// assert_eq!(node_ptr, &node as *const Node);
+/// ```
+///
+/// A reference to an initialized element can be taken at the end with `.&?`.
+///
+/// ```
+/// use element_ptr::element_ptr;
+/// use std::ptr::NonNull;
+///
+/// struct Node {
+///     name: String,
+///     parent: *const Node,
+/// }
+///
+/// let root = Node { name: "root".into(), parent: std::ptr::null() };
+/// let child = Node { name: "child".into(), parent: &root };
+///
+/// let parent: Option<&Node> = unsafe { element_ptr!(&child as *const Node => .parent.* .&?) };
+/// assert_eq!(parent.map(|node| node.name.as_str()), Some("root"));
+///
+/// let grandparent: Option<&Node> = unsafe { element_ptr!(&root as *const Node => .parent.* .&?) };
+/// assert!(grandparent.is_none());
+///
+/// // a `NonNull` is never null, so it just gives a reference.
+/// let name: &String = unsafe { element_ptr!(NonNull::from(&child) => .name.&?) };
+/// assert_eq!(name, "child");
+/// ```
+///
+/// Since a null pointer gives `None`, this works well for walking a linked list.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// struct Node {
+///     value: u32,
+///     next: *const Node,
+/// }
+///
+/// let third = Node { value: 3, next: std::ptr::null() };
+/// let second = Node { value: 2, next: &third };
+/// let first = Node { value: 1, next: &second };
+///
+/// let mut values = Vec::new();
+/// let mut node = Some(&first);
+/// while let Some(current) = node {
+///     values.push(current.value);
+///     node = unsafe { element_ptr!(current => .next.* .&?) };
+/// }
+///
+/// assert_eq!(values, [1, 2, 3]);
+/// ```
+///
+/// `.&mut?` gives a mutable reference in the same way.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// struct Inventory {
+///     items: Vec<&'static str>,
+///     gold: u32,
+/// }
+///
+/// let mut inventory = Inventory { items: vec!["sword"], gold: 10 };
+/// let ptr = &mut inventory as *mut Inventory;
+///
+/// let items: Option<&mut Vec<&str>> = unsafe { element_ptr!(ptr => .items.&mut?) };
+/// items.unwrap().push("shield");
+///
+/// let gold: Option<&mut u32> = unsafe { element_ptr!(ptr => .gold.&mut?) };
+/// *gold.unwrap() -= 4;
+///
+/// assert_eq!(inventory.items, ["sword", "shield"]);
+/// assert_eq!(inventory.gold, 6);
+/// ```
+///
+/// A pointer that may be null can be checked with `.?` before going through it. The macro then
+/// returns an `Option`, which is `None` if any of the checked pointers were null.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// struct Node {
+///     value: u32,
+///     next: *const Node,
+/// }
+///
+/// let third = Node { value: 3, next: std::ptr::null() };
+/// let second = Node { value: 2, next: &third };
+/// let first = Node { value: 1, next: &second };
+/// let ptr = &first as *const Node;
+///
+/// let value: Option<u32> = unsafe { element_ptr!(ptr => .next.* .? .next.* .? .value.*) };
+/// assert_eq!(value, Some(3));
+///
+/// let value = unsafe { element_ptr!(ptr => .next.* .? .next.* .? .next.* .? .value.*) };
+/// assert_eq!(value, None);
+/// ```
+///
+/// A range inside of the brackets gives a slice pointer to part of an array or slice.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// struct Frame {
+///     header: [u8; 4],
+///     body: [u8; 12],
+/// }
+///
+/// let frame = Frame { header: *b"FRM1", body: *b"hello, world" };
+/// let ptr = &frame as *const Frame;
+///
+/// let version = unsafe { &*element_ptr!(ptr => .header [3..]) };
+/// let greeting = unsafe { &*element_ptr!(ptr => .body [..5]) };
+/// let target = unsafe { &*element_ptr!(ptr => .body [7..=11]) };
+///
+/// assert_eq!(version, b"1");
+/// assert_eq!(greeting, b"hello");
+/// assert_eq!(target, b"world");
+/// ```
+///
+/// An inclusive range that ends at `usize::MAX` panics, instead of wrapping around to an
+/// empty range.
+///
+/// ```should_panic
+/// # use element_ptr::element_ptr;
+/// let data = &[1u8, 2, 3] as *const [u8];
+/// let end = usize::MAX;
+/// let rest = unsafe { element_ptr!(data => [1..=end]) };
+/// ```
+///
+/// The number of elements behind an array or slice pointer can be found with `.len()`.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// struct Table {
+///     len: usize,
+///     rows: [u64; 8],
+/// }
+///
+/// let table = Table { len: 5, rows: [0; 8] };
+/// let ptr = &table as *const Table;
+///
+/// let capacity = unsafe { element_ptr!(ptr => .rows.len()) };
+/// let used = unsafe { element_ptr!(ptr => .len.*) };
+/// let unused = unsafe { element_ptr!(ptr => .rows [used..].len()) };
+///
+/// assert_eq!(capacity, 8);
+/// assert_eq!(unused, 3);
+/// ```
+///
+/// A reference can be used as the base directly, and a `&mut T` can be written through.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// struct Stats {
+///     samples: [f32; 3],
+///     total: f32,
+/// }
+///
+/// let mut stats = Stats { samples: [1.5, 2.0, 0.5], total: 0.0 };
+///
+/// let total = unsafe {
+///     let samples = element_ptr!(&stats => .samples.*);
+///     samples.iter().sum()
+/// };
+/// unsafe { element_ptr!(&mut stats => .total <- total) };
+///
+/// assert_eq!(stats.total, 4.0);
+/// ```
+///
+/// The address of an element can be taken at the end with `.addr()`, such as for logging.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// #[repr(C)]
+/// struct Registers {
+///     status: u32,
+///     control: u32,
+/// }
+///
+/// let registers = Registers { status: 0, control: 0 };
+/// let ptr = &registers as *const Registers;
+///
+/// let status = unsafe { element_ptr!(ptr => .status.addr()) };
+/// let control = unsafe { element_ptr!(ptr => .control.addr()) };
+///
+/// assert_eq!(status, ptr.addr());
+/// assert_eq!(control - status, 4);
+/// ```
+///
+/// Starting from a reference with `ref` gives a [`NonNull<T>`].
+///
+/// ```
+/// use element_ptr::element_ptr;
+/// use std::ptr::NonNull;
+///
+/// struct Config {
+///     name: &'static str,
+///     limits: [u32; 3],
+/// }
+///
+/// let config = Config { name: "default", limits: [8, 16, 32] };
+///
+/// let limit: NonNull<u32> = unsafe { element_ptr!(ref &config => .limits[1]) };
+/// assert_eq!(unsafe { limit.read() }, 16);
+///
+/// let name = unsafe { element_ptr!(ref &config => .name.*) };
+/// assert_eq!(name, "default");
+/// ```
+///
+/// Starting from a [`Pin`] with `pin` keeps the result pinned.
+///
+/// ```
+/// use element_ptr::element_ptr;
+/// use std::{marker::PhantomPinned, pin::Pin};
+///
+/// struct Timer {
+///     deadline: u64,
+///     _pinned: PhantomPinned,
+/// }
+///
+/// struct Task {
+///     polls: u32,
+///     timer: Timer,
+/// }
+///
+/// let mut task = Box::pin(Task {
+///     polls: 0,
+///     timer: Timer { deadline: 100, _pinned: PhantomPinned },
+/// });
+///
+/// // Safety: `timer` is structurally pinned in `Task`.
+/// let timer = unsafe { element_ptr!(pin task.as_mut() => .timer) };
+/// let deadline = unsafe { element_ptr!(pin timer => .deadline) };
+///
+/// let mut deadline: Pin<&mut u64> = unsafe { deadline.into_pin_mut() };
+/// *deadline += 50;
+///
+/// assert_eq!(task.timer.deadline, 150);
+/// ```
+///
+/// ```compile_fail
+/// use element_ptr::element_ptr;
+///
+/// let value = Box::pin((1u8, 2u16));
+///
+/// // error: a `pin` projection cannot use `.*`, since the value that is read is not pinned
+/// let second = unsafe { element_ptr!(pin value.as_ref() => .1 .*) };
+/// ```
+///
+/// ```compile_fail
+/// use element_ptr::element_ptr;
+///
+/// let value = Box::pin((1u8, 2u16));
+///
+/// // error: a `pin` projection always returns a pinned pointer, so it cannot be kept with `~`
+/// let second = unsafe { element_ptr!(pin value.as_ref() => .1 ~) };
+/// ```
+///
+/// Reading a value that is not a pointer must be the final access, since there is nothing
+/// left to access through. Anything after it is a compile error.
+///
+/// ```compile_fail
+/// use element_ptr::element_ptr;
+///
+/// struct Counter {
+///     count: usize,
+///     history: [usize; 4],
+/// }
+///
+/// let counter = Counter { count: 1, history: [0; 4] };
+/// let ptr = &counter as *const Counter;
+///
+/// // error: `usize` is not a pointer, so nothing can be accessed through it
+/// let value = unsafe { element_ptr!(ptr => .count.* [0]) };
+/// ```
+///
+/// An index access needs an expression inside of its brackets.
+///
+/// ```compile_fail
+/// use element_ptr::element_ptr;
+///
+/// let history = [0usize; 4];
+/// let ptr = &history as *const [usize; 4];
+///
+/// // error: expected an index expression inside of these brackets
+/// let first = unsafe { element_ptr!(ptr => []) };
+/// ```
+///
+/// A typed field access checks the type of the field, so that a change to the type of the
+/// field can not silently change what is read.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// struct Header {
+///     len: u16,
+///     kind: u8,
+/// }
+///
+/// let header = Header { len: 3, kind: 1 };
+/// let ptr = &header as *const Header;
+///
+/// let len: u16 = unsafe { element_ptr!(ptr => .len: u16 .*) };
+/// assert_eq!(len, 3);
+/// ```
+///
+/// ```compile_fail,E0308
+/// # use element_ptr::element_ptr;
+/// # struct Header {
+/// #     len: u16,
+/// #     kind: u8,
+/// # }
+/// # let header = Header { len: 3, kind: 1 };
+/// # let ptr = &header as *const Header;
+/// // error: expected `Pointer<_, u32>`, found `Pointer<Const, u16>`
+/// let len = unsafe { element_ptr!(ptr => .len: u32 .*) };
+/// ```
+///
+/// A slice can be split into two at once, which is useful for buffers that are
+/// filled from the front.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// struct Buffer {
+///     filled: usize,
+///     data: [u8; 6],
+/// }
+///
+/// let buffer = Buffer { filled: 4, data: [1, 2, 3, 4, 0, 0] };
+/// let data = unsafe { element_ptr!(&buffer => .data) } as *const [u8];
+///
+/// let (filled, free) = unsafe { element_ptr!(data => [..buffer.filled | buffer.filled..]) };
+/// assert_eq!((filled.len(), free.len()), (4, 2));
+/// assert_eq!(unsafe { &*filled }, [1, 2, 3, 4]);
+///
+/// // either side may be empty.
+/// let (all, rest) = unsafe { element_ptr!(data => [..6 | 6..]) };
+/// assert_eq!((all.len(), rest.len()), (6, 0));
+/// ```
+///
+/// A `mid` that is past the end of the slice panics instead of creating a pointer that is
+/// out of bounds.
+///
+/// ```should_panic
+/// # use element_ptr::element_ptr;
+/// let data = &[1u8, 2, 3] as *const [u8];
+/// let (head, tail) = unsafe { element_ptr!(data => [..4 | 4..]) };
+/// ```
+///
+/// The pointer wrapper can be kept with `~`, and then used with its own methods.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// struct Samples {
+///     rate: u32,
+///     data: [i16; 4],
+/// }
+///
+/// let mut samples = Samples { rate: 44100, data: [1, 2, 3, 4] };
+/// let ptr = &mut samples as *mut Samples;
+///
+/// unsafe {
+///     let first = element_ptr!(ptr => .data[0] ~);
+///     let last: *mut i16 = first.add(3).into_inner();
+///     assert_eq!(first.read(), 1);
+///     assert_eq!(*last, 4);
+/// }
+/// ```
+///
+/// Values can be written directly at the end of the accesses with `<- value`.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// struct Config {
+///     version: u16,
+///     ports: [u16; 3],
+/// }
+///
+/// let mut config = Config { version: 0, ports: [0; 3] };
+/// let ptr = &mut config as *mut Config;
+///
+/// unsafe {
+///     element_ptr!(ptr => .version <- 2);
+///     for i in 0..3 {
+///         element_ptr!(ptr => .ports[i] <- 8080 + i as u16);
+///     }
+/// }
+///
+/// assert_eq!(config.version, 2);
+/// assert_eq!(config.ports, [8080, 8081, 8082]);
+/// ```
+///
+/// Memory mapped registers can be read with `.*volatile` and written with `<-{vol}`, which are never
+/// elided or reordered with other volatile accesses by the compiler.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// #[repr(C)]
+/// struct Uart {
+///     status: u32,
+///     data: u32,
+///     // the next device in a chain of them.
+///     next: *const Uart,
+/// }
+///
+/// let second = Uart { status: 0b10, data: 0x42, next: std::ptr::null() };
+/// let mut first = Uart { status: 0b01, data: 0, next: &second };
+/// let uart = &mut first as *mut Uart;
+///
+/// unsafe {
+///     assert_eq!(element_ptr!(uart => .status.*volatile), 0b01);
+///     assert_eq!(element_ptr!(uart => .next.*volatile.data.*volatile), 0x42);
+///     element_ptr!(uart => .data <-{vol} 0x43);
+/// }
+/// assert_eq!(first.data, 0x43);
+///
+/// // without the braces, `vol` is just a normal variable.
+/// let vol = 0x50;
+/// unsafe { element_ptr!(uart => .data <- vol - 1) };
+/// assert_eq!(first.data, 0x4F);
+/// ```
+///
+/// An erased pointer can be given its real type right in the base with `erased`.
+///
+/// ```
+/// use element_ptr::element_ptr;
+/// use std::ffi::c_void;
+///
+/// struct Context {
+///     calls: u32,
+///     name: &'static str,
+/// }
+///
+/// // a callback that gets its context back as an erased pointer.
+/// unsafe extern "C" fn callback(user_data: *mut c_void) {
+///     let calls = element_ptr!(erased user_data as Context => .calls);
+///     *calls += 1;
+/// }
+///
+/// let mut context = Context { calls: 0, name: "example" };
+/// let user_data = &mut context as *mut Context as *mut c_void;
+///
+/// unsafe {
+///     callback(user_data);
+///     callback(user_data);
+///     assert_eq!(element_ptr!(erased user_data as Context => .name.*), "example");
+/// }
+/// assert_eq!(context.calls, 2);
+/// ```
+///
+/// An `erased` base must give the type with `as T`.
+///
+/// ```compile_fail
+/// use element_ptr::element_ptr;
+/// use std::ffi::c_void;
+///
+/// let mut value = 1u32;
+/// let user_data = &mut value as *mut u32 as *mut c_void;
+///
+/// unsafe { element_ptr!(erased user_data => .*) };
+/// ```
+///
+/// Each cast in a run of casts is still type checked, so a cast to an unsized type is rejected
+/// even if another cast follows it.
+///
+/// ```compile_fail
+/// use element_ptr::element_ptr;
+///
+/// let value = 0x0102_0304u32;
+/// let ptr = &value as *const u32;
+///
+/// let same: *const u32 = unsafe { element_ptr!(ptr => as [u8] => as u32) };
+/// ```
+///
+/// A nested array like `[[T; C]; R]` can be indexed with `[row, col]`. The rows are laid out one
+/// after another (row-major order), so this reaches the same element as `[row][col]`.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// struct Matrix {
+///     scale: f32,
+///     cells: [[u16; 3]; 2],
+/// }
+///
+/// let mut matrix = Matrix {
+///     scale: 1.0,
+///     cells: [[1, 2, 3], [4, 5, 6]],
+/// };
+/// let ptr = &mut matrix as *mut Matrix;
+///
+/// unsafe {
+///     for row in 0..2 {
+///         for col in 0..3 {
+///             assert_eq!(
+///                 element_ptr!(ptr => .cells[row, col]),
+///                 element_ptr!(ptr => .cells[row][col]),
+///             );
+///         }
+///     }
+///     // the flat index is `1 * 3 + 2`.
+///     element_ptr!(ptr => .cells[1, 2]).write(60);
+///     assert_eq!(element_ptr!(ptr => .cells[1][2].*), 60);
+/// }
+/// assert_eq!(matrix.cells, [[1, 2, 3], [4, 5, 60]]);
+/// ```
+///
+/// A flat index that overflows a `usize` panics instead of wrapping around.
+///
+/// ```should_panic
+/// # use element_ptr::element_ptr;
+/// let cells = [[0u8; 4]; 2];
+/// let row = usize::MAX / 2;
+/// unsafe { element_ptr!(&cells => [row, 0]) };
+/// ```
+///
+/// Values in packed structs may not be aligned, so they must be read with `.*unaligned`.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// #[repr(C, packed)]
+/// struct Packet {
+///     kind: u8,
+///     next: *const Packet,
+///     len: u32,
+/// }
+///
+/// let second = Packet { kind: 2, next: std::ptr::null(), len: 64 };
+/// let first = Packet { kind: 1, next: &second, len: 0 };
+/// let ptr = &first as *const Packet;
+///
+/// let len = unsafe { element_ptr!(ptr => .next.*unaligned.len.*unaligned) };
+///
+/// assert_eq!(len, 64);
+/// ```
+///
+/// They can also be written with `<-{unaligned}`.
+///
+/// ```
+/// use element_ptr::element_ptr;
+/// use std::mem::MaybeUninit;
+///
+/// #[repr(C, packed)]
+/// struct Header {
+///     version: u8,
+///     // at an odd offset.
+///     timestamp: u64,
+/// }
+///
+/// let mut slot = MaybeUninit::<Header>::uninit();
+/// let unaligned = 3;
+///
+/// let header = unsafe {
+///     element_ptr!(uninit &mut slot => .version <-{unaligned} 1);
+///     element_ptr!(uninit &mut slot => .timestamp <-{unaligned} 0x0123_4567_89AB_CDEF);
+///     // without the braces, `unaligned` is just a normal variable.
+///     element_ptr!(uninit &mut slot => .version <- unaligned - 1);
+///     slot.assume_init()
+/// };
+///
+/// assert_eq!({ header.version }, 2);
+/// assert_eq!({ header.timestamp }, 0x0123_4567_89AB_CDEF);
+/// ```
+///
+/// The wrapping offsets allow a pointer to go out of bounds before it is brought back.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// let ring = [10u32, 20, 30, 40];
+/// let ptr = ring.as_ptr();
+///
+/// // one past the start, going through a pointer that is out of bounds.
+/// let wrapped = unsafe { element_ptr!(ptr => ~- 3 ~+ 4 .*) };
+/// assert_eq!(wrapped, 20);
+///
+/// let bytes = unsafe { element_ptr!(ptr => u8~+ 64 u8~- 56 .*) };
+/// assert_eq!(bytes, 30);
+///
+/// // a trailing `~` still keeps the pointer.
+/// let kept = unsafe { element_ptr!(ptr => ~+ 1 ~) };
+/// assert_eq!(unsafe { kept.read() }, 20);
+/// ```
+///
+/// An offset that may be negative can be applied with `@`.
+///
+/// ```
+/// use element_ptr::element_ptr;
+///
+/// let samples = [1i16, 2, 3, 4, 5];
+/// let ptr = &samples as *const [i16; 5];
+///
+/// let delta: isize = -3;
+/// let value = unsafe { element_ptr!(ptr => [4] @ (delta) .*) };
+/// assert_eq!(value, 2);
+///
+/// let value = unsafe { element_ptr!(ptr => [0] @ 4 u8@ (-4) .*) };
+/// assert_eq!(value, 3);
+/// ```
+///
+// the following links need to be explicitly put because rustdoc cannot refer to pointer methods.
+/// [addr_of!]: core::ptr::addr_of!
+/// [read]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read
+/// [as_ref]: https://doc.rust-lang.org/core/primitive.pointer.html#method.as_ref
+/// [as_mut]: https://doc.rust-lang.org/core/primitive.pointer.html#method.as_mut
+/// [len]: https://doc.rust-lang.org/core/primitive.pointer.html#method.len
+/// [addr]: https://doc.rust-lang.org/core/primitive.pointer.html#method.addr
+/// [is_null]: https://doc.rust-lang.org/core/primitive.pointer.html#method.is_null
+/// [with_addr]: https://doc.rust-lang.org/core/primitive.pointer.html#method.with_addr
+/// [map_addr]: https://doc.rust-lang.org/core/primitive.pointer.html#method.map_addr
+/// [UnsafeCell::raw_get]: core::cell::UnsafeCell::raw_get
+/// [`UnsafeCell<T>`]: core::cell::UnsafeCell
+/// [`Cell<T>`]: core::cell::Cell
+/// [`MaybeUninit<T>`]: core::mem::MaybeUninit
+/// [read_volatile]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read_volatile
+/// [read_unaligned]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read_unaligned
+/// [write]: https://doc.rust-lang.org/core/primitive.pointer.html#method.write
+/// [write_volatile]: https://doc.rust-lang.org/core/primitive.pointer.html#method.write_volatile
+/// [write_unaligned]: https://doc.rust-lang.org/core/primitive.pointer.html#method.write_unaligned
+/// [add]: https://doc.rust-lang.org/core/primitive.pointer.html#method.add
+/// [sub]: https://doc.rust-lang.org/core/primitive.pointer.html#method.sub
+/// [byte_add]: https://doc.rust-lang.org/core/primitive.pointer.html#method.byte_add
+/// [byte_sub]: https://doc.rust-lang.org/core/primitive.pointer.html#method.byte_sub
+/// [offset]: https://doc.rust-lang.org/core/primitive.pointer.html#method.offset
+/// [byte_offset]: https://doc.rust-lang.org/core/primitive.pointer.html#method.byte_offset
+/// [align_offset]: https://doc.rust-lang.org/core/primitive.pointer.html#method.align_offset
+/// [wrapping_add]: https://doc.rust-lang.org/core/primitive.pointer.html#method.wrapping_add
+/// [wrapping_sub]: https://doc.rust-lang.org/core/primitive.pointer.html#method.wrapping_sub
+/// [wrapping_byte_add]: https://doc.rust-lang.org/core/primitive.pointer.html#method.wrapping_byte_add
+/// [wrapping_byte_sub]: https://doc.rust-lang.org/core/primitive.pointer.html#method.wrapping_byte_sub
+/// [`offset()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.offset
+/// [offsetreq]: https://doc.rust-lang.org/core/primitive.pointer.html#safety-2
+/// [readreq]: https://doc.rust-lang.org/core/ptr/fn.read.html#safety
+/// [writereq]: https://doc.rust-lang.org/core/ptr/fn.write.html#safety
+/// [cast::\<T>]: https://doc.rust-lang.org/core/primitive.pointer.html#method.cast
+/// [cast_mut]: https://doc.rust-lang.org/core/primitive.pointer.html#method.cast_mut
+/// [cast_const]: https://doc.rust-lang.org/core/primitive.pointer.html#method.cast_const
+/// [split_at]: https://doc.rust-lang.org/core/primitive.slice.html#method.split_at
+/// [`*const T`]: https://doc.rust-lang.org/core/primitive.pointer.html
+/// [`*mut T`]: https://doc.rust-lang.org/core/primitive.pointer.html
+/// [`NonNull<T>`]: core::ptr::NonNull
+/// [`&mut MaybeUninit<T>`]: core::mem::MaybeUninit
+/// [`MaybeUninit::as_mut_ptr()`]: core::mem::MaybeUninit::as_mut_ptr
+/// [`Pin`]: core::pin::Pin
+/// [`Pin<&T>`]: core::pin::Pin
+/// [`Pin<&mut T>`]: core::pin::Pin
+// #[cfg(not(doctest))] // just don't doctest any of these. Macros are way too hard to do.
+pub use element_ptr_macro::element_ptr;
+
+/// The same as [`element_ptr!`], but casts the resulting pointer to a pointer to `u8`.
+///
+/// The kind of pointer is kept the same, so a [`*const T`] becomes a `*const u8`, a [`*mut T`]
+/// becomes a `*mut u8`, and a [`NonNull<T>`] becomes a `NonNull<u8>`. This is the same as ending
+/// the accesses with `as u8`, and is useful for passing an element to `memcpy`-style APIs.
+///
+/// The accesses must end with a pointer, so this can't be used with a dereference as the final
+/// access, or with a trailing `~`.
+///
+/// # Safety
+/// The same requirements as [`element_ptr!`] apply.
+///
+/// # Examples
+///
+/// ```
+/// use element_ptr::element_ptr_bytes;
+/// use std::ffi::c_void;
+///
+/// extern "C" {
+///     fn memcpy(dest: *mut c_void, src: *const c_void, n: usize) -> *mut c_void;
+/// }
+///
+/// struct Packet {
+///     id: u16,
+///     payload: [u32; 4],
+/// }
+///
+/// let src = Packet { id: 1, payload: [1, 2, 3, 4] };
+/// let mut dst = Packet { id: 2, payload: [0; 4] };
+///
+/// let src_ptr = &src as *const Packet;
+/// let dst_ptr = &mut dst as *mut Packet;
+///
+/// unsafe {
+///     let from: *const u8 = element_ptr_bytes!(src_ptr => .payload[1]);
+///     let to: *mut u8 = element_ptr_bytes!(dst_ptr => .payload[2]);
+///     memcpy(to.cast(), from.cast(), 2 * size_of::<u32>());
+/// }
+///
+/// assert_eq!(dst.payload, [0, 0, 2, 3]);
+/// ```
+///
+/// [`*const T`]: https://doc.rust-lang.org/core/primitive.pointer.html
+/// [`*mut T`]: https://doc.rust-lang.org/core/primitive.pointer.html
+/// [`NonNull<T>`]: core::ptr::NonNull
+pub use element_ptr_macro::element_ptr_bytes;
+
+/// Returns the offset in bytes of an inner element from the start of a type.
+///
+/// The general syntax is
+#[cfg_attr(doctest, doc = "````notest")] // don't doctest this.
+/// ```
+/// element_offset!(Type => /* element accesses */ )
+/// ````
+/// where the element accesses are the same as in [`element_ptr!`], starting from a pointer to `Type`.
+///
+/// The expansion only uses `const` operations, so this can be used to build tables
+/// of offsets in `const` and `static` items.
+///
+/// # Safety
+/// The same requirements as [`element_ptr!`] apply, relative to a `Type` that lives on the stack.
+/// Besides that,
+/// * The dereference access (`.*`) must not be used, because the `Type` is uninitialized.
+/// * The final element must not be before the start of `Type`.
+///
+/// Inside of a `const` context, violating any of these will cause a compile error.
+///
+/// # Examples
+///
+/// ```
+/// use element_ptr::element_offset;
+///
+/// #[repr(C)]
+/// struct Header {
+///     magic: u32,
+///     version: u16,
+///     flags: [u8; 4],
+/// }
+///
+/// const OFFSETS: [usize; 3] = unsafe {
+///     [
+///         element_offset!(Header => .magic),
+///         element_offset!(Header => .version),
+///         element_offset!(Header => .flags[2]),
+///     ]
+/// };
+///
+/// assert_eq!(OFFSETS, [0, 4, 8]);
+/// ```
+pub use element_ptr_macro::element_offset;
+
+/// Returns the distance in bytes between two elements reached from the same base pointer.
+///
+/// The general syntax is
+#[cfg_attr(doctest, doc = "````notest")] // don't doctest this.
+/// ```
+/// element_offset_from!(ptr => /* first element accesses */ ; /* second element accesses */ )
+/// ````
+/// where both lists of element accesses are the same as in [`element_ptr!`], starting from `ptr`.
+/// The base pointer is only evaluated once.
+///
+/// The result is an `isize` that is the address of the second element minus the address of
+/// the first one, so it is positive when the second element comes after the first. This is
+/// useful for checking the layout of a type against a serialization format.
+///
+/// # Safety
+/// The same requirements as [`element_ptr!`] apply to both lists of accesses.
+/// Besides that, both elements must be in the same [allocated object], which is the
+/// same requirement that [`pointer::byte_offset_from()`] has.
+///
+/// # Examples
+///
+/// ```
+/// use element_ptr::element_offset_from;
+///
+/// #[repr(C)]
+/// struct Record {
+///     tag: u8,
+///     lengths: [u16; 4],
+///     payload: [u8; 8],
+/// }
+///
+/// let record = Record { tag: 0, lengths: [0; 4], payload: [0; 8] };
+/// let ptr = &record as *const Record;
+///
+/// unsafe {
+///     assert_eq!(element_offset_from!(ptr => .lengths[1]; .payload[2]), 8);
+///     assert_eq!(element_offset_from!(ptr => .payload; .tag), -10);
+/// }
+/// ```
+///
+/// [allocated object]: https://doc.rust-lang.org/core/ptr/index.html#allocated-object
+/// [`pointer::byte_offset_from()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.byte_offset_from
+pub use element_ptr_macro::element_offset_from;
+
+/// Creates a function that does the same element accesses on any base pointer.
+///
+/// The general syntax is
+#[cfg_attr(doctest, doc = "````notest")] // don't doctest this.
+/// ```
+/// element_projector!(PtrType => /* element accesses */ )
+/// ````
+/// where `PtrType` is one of the pointer types accepted by [`element_ptr!`], and the element
+/// accesses are the same as in [`element_ptr!`].
+///
+/// Any index may be written as `[_]`, which turns it into a parameter of the function.
+/// The resulting function has the signature
+#[cfg_attr(doctest, doc = "````notest")] // don't doctest this.
+/// ```
+/// unsafe fn(PtrType, /* one `usize` for each `[_]`, in order */ ) -> /* result */
+/// ````
+/// Because the projection is compiled into a single function, it can be reused
+/// on many different base pointers.
+/// None of the other expressions in the accesses may use local variables.
+///
+/// # Safety
+/// Calling the function has the same requirements as [`element_ptr!`].
+///
+/// # Examples
+///
+/// ```
+/// use element_ptr::element_projector;
+///
+/// struct Grid {
+///     id: u32,
+///     cells: [u8; 4],
+/// }
+///
+/// let grids = [
+///     Grid { id: 0, cells: [1, 2, 3, 4] },
+///     Grid { id: 1, cells: [5, 6, 7, 8] },
+/// ];
+///
+/// let cell: unsafe fn(*const Grid, usize) -> *const u8 =
+///     element_projector!(*const Grid => .cells[_]);
+///
+/// for grid in &grids {
+///     for index in 0..4 {
+///         let expected = grid.id as u8 * 4 + index as u8 + 1;
+///         assert_eq!(unsafe { *cell(grid, index) }, expected);
+///     }
+/// }
+/// ```
+pub use element_ptr_macro::element_projector;
+
+/// Borrows an inner element of a borrowed value, using the same accesses as [`element_ptr!`].
+///
+/// The general syntax is
+#[cfg_attr(doctest, doc = "````notest")] // don't doctest this.
+/// ```
+/// element_ref!(&value => /* element accesses */ )
+/// element_ref!(&mut value => /* element accesses */ )
+/// ````
+/// which returns a `&T` or a `&mut T` to the last accessed element. The base must be written as a
+/// borrow, so a reference `r` that is already borrowed is written as `&*r` or `&mut *r`.
+///
+/// Only field (`.field` and `.0`), index (`[index]` and `[start..end]`), and dereference (`.*`)
+/// accesses can be used. These are turned into a single place expression, like
+/// `&mut (*value.a).b[i]`, so no references to unrelated fields are created, and the borrow checker
+/// verifies the whole projection. Indexing uses [`Index`](core::ops::Index), so it panics when
+/// the index is out of bounds instead of being undefined behavior.
+///
+/// Like in [`element_ptr!`], `.*` follows the reference in the current element, and the accesses
+/// after it continue from there. It can't be the final access, since the macro always returns a
+/// reference.
+///
+/// Unlike the other macros, this is safe to use, because the value must already be
+/// fully initialized to be borrowed.
+///
+/// # Examples
+///
+/// ```
+/// use element_ptr::element_ref;
+///
+/// struct Player {
+///     name: &'static str,
+///     stats: [u32; 3],
+/// }
+///
+/// struct Game<'a> {
+///     players: Vec<Player>,
+///     scores: (u32, &'a mut [u32]),
+/// }
+///
+/// let mut scores = [1, 2, 3];
+/// let mut game = Game {
+///     players: vec![Player { name: "ferris", stats: [10, 20, 30] }],
+///     scores: (0, &mut scores),
+/// };
+///
+/// let stat: &mut u32 = element_ref!(&mut game => .players[0].stats[1]);
+/// *stat += 1;
+///
+/// // `.*` follows the reference in the tuple.
+/// let score: &mut u32 = element_ref!(&mut game => .scores.1 .*[2]);
+/// *score *= 2;
+///
+/// let name: &&str = element_ref!(&game => .players[0].name);
+///
+/// assert_eq!(*name, "ferris");
+/// assert_eq!(game.players[0].stats, [10, 21, 30]);
+/// assert_eq!(scores, [1, 2, 6]);
+/// ```
+///
+/// Because the projection is checked by the borrow checker, a mutable projection through a
+/// shared reference is a compile error.
+///
+/// ```compile_fail
+/// use element_ptr::element_ref;
+///
+/// struct Pair<'a> {
+///     left: &'a [u32; 2],
+///     right: u32,
+/// }
+///
+/// let values = [1, 2];
+/// let mut pair = Pair { left: &values, right: 3 };
+///
+/// let left: &mut u32 = element_ref!(&mut pair => .left.*[0]);
+/// ```
+///
+/// A final `.*` is a compile error, since it would return the value instead of a reference.
+///
+/// ```compile_fail
+/// use element_ptr::element_ref;
+///
+/// let pair = (1u32, &2u32);
+///
+/// let value = element_ref!(&pair => .1 .*);
+/// ```
+pub use element_ptr_macro::element_ref;
+
+/// The pointer wrapper that the macros use internally, which is returned by a trailing `~`.
+///
+/// [`Pointer<M, T>`](helper::Pointer) is a pointer to a `T` that keeps track of its mutability
+/// with `M`, which is [`Const`](helper::Const), [`Mut`](helper::Mut), or
+/// [`NonNull`](helper::NonNull). It has most of the methods of raw pointers, along with
+/// many more for reading and writing elements. A pointer can be wrapped with
+/// [`new_pointer()`](helper::new_pointer) and unwrapped again with `into_inner()`.
+pub mod helper {
+    use core::{
+        alloc::Layout,
+        cell::{Cell, UnsafeCell},
+        marker::PhantomData,
+        mem::{ManuallyDrop, MaybeUninit},
+        ops::{BitAnd, BitOr, Not},
+        pin::Pin,
+        sync::atomic::{AtomicU32, Ordering},
+    };
+    /// A trait that describes the mutability of a pointer.
+    ///
+    /// # Safety
+    /// * The types that implement this trait should act as a "brand" on
+    ///   the pointer type, uniquely identifying it.
+    /// * `Var<T>` should have the same variance as `Raw<T>`.
+    pub unsafe trait Mutability {
+        type Var<T: ?Sized>;
+        type Raw<T: ?Sized>: IsPtr<M = Self, T = T>;
+    }
+    /// A trait that describes a pointer.
+    ///
+    /// # Safety
+    /// * This should only be implemented on a pointer type. This type must be allowed to be
+    ///   transmuted to a `*const T` and read from.
+    /// * If `M` is [`CanWrite`], the transmuted pointer must be allowed to write to its pointee.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` is not a pointer",
+        label = "expected a `*const T`, `*mut T`, or `NonNull<T>` here"
+    )]
+    pub unsafe trait IsPtr: Copy {
+        type M: Mutability;
+        type T: ?Sized;
+    }
+    /// A trait that describes what the accesses can start from, which is any of the
+    /// pointers that implement [`IsPtr`], or a reference.
+    ///
+    /// A `&T` starts the accesses as a `*const T`, and a `&mut T` as a `*mut T`.
+    ///
+    /// # Safety
+    /// * This type must be allowed to be transmuted to a `*const T` and read from.
+    /// * If `M` is [`CanWrite`], the transmuted pointer must be allowed to write to its pointee.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` is not a pointer",
+        label = "expected a `*const T`, `*mut T`, `NonNull<T>`, or a reference here"
+    )]
+    pub unsafe trait IsBasePtr {
+        type M: Mutability;
+        type T: ?Sized;
+    }
+    /// A pointer that was read out of memory in the middle of the accesses,
+    /// and is then accessed through.
+    ///
+    /// This is the same as [`IsBasePtr`], and only exists to give a better error message
+    /// when the value that was read is not a pointer.
+    ///
+    /// # Safety
+    /// * This must only be implemented on types that implement [`IsBasePtr`].
+    #[doc(hidden)]
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` is not a pointer, so nothing can be accessed through it",
+        label = "this reads a `{Self}`",
+        note = "a dereference that reads a value which is not a pointer must be the final access"
+    )]
+    pub unsafe trait IsReadPtr: IsBasePtr {}
+
+    /// A trait to mark which mutabilities may be written through.
+    ///
+    /// # Safety
+    /// * `Raw<T>` must be a pointer type that is allowed to write to its pointee.
+    #[diagnostic::on_unimplemented(
+        message = "cannot write through a `*const` pointer",
+        label = "this pointer is not mutable"
+    )]
+    pub unsafe trait CanWrite: Mutability {}
+
+    pub enum Const {}
+    pub enum Mut {}
+    // NonNull is safe here because all of the methods on `Pointer`
+    // and related freestanding functions all require the pointer
+    // to stay within the bounds of the allocated object.
+    // Because the null address is not ever part of an allocated object,
+    // this means that as long as a pointer is created from an existing `NonNull`,
+    // all uses that would invalidate the `NonNull` would be UB regardless.
+    pub enum NonNull {}
+
+    unsafe impl Mutability for Const {
+        type Var<T: ?Sized> = fn() -> T;
+        type Raw<T: ?Sized> = *const T;
+    }
+    unsafe impl Mutability for Mut {
+        type Var<T: ?Sized> = fn(T) -> T;
+        type Raw<T: ?Sized> = *mut T;
+    }
+    unsafe impl Mutability for NonNull {
+        type Var<T: ?Sized> = fn() -> T;
+        type Raw<T: ?Sized> = core::ptr::NonNull<T>;
+    }
+
+    unsafe impl CanWrite for Mut {}
+    unsafe impl CanWrite for NonNull {}
+
+    unsafe impl<T: ?Sized> IsPtr for *mut T {
+        type M = Mut;
+        type T = T;
+    }
+    unsafe impl<T: ?Sized> IsPtr for *const T {
+        type M = Const;
+        type T = T;
+    }
+    unsafe impl<T: ?Sized> IsPtr for core::ptr::NonNull<T> {
+        type M = NonNull;
+        type T = T;
+    }
+
+    unsafe impl<T: ?Sized> IsBasePtr for *mut T {
+        type M = Mut;
+        type T = T;
+    }
+    unsafe impl<T: ?Sized> IsBasePtr for *const T {
+        type M = Const;
+        type T = T;
+    }
+    unsafe impl<T: ?Sized> IsBasePtr for core::ptr::NonNull<T> {
+        type M = NonNull;
+        type T = T;
+    }
+    // References are transmuted to raw pointers without being reborrowed, so the pointer
+    // keeps the exact provenance of the reference, like `addr_of!(*r)` or `addr_of_mut!(*r)`.
+    // This means that a pointer from a `&mut T` can still be written through.
+    unsafe impl<T: ?Sized> IsBasePtr for &T {
+        type M = Const;
+        type T = T;
+    }
+    unsafe impl<T: ?Sized> IsBasePtr for &mut T {
+        type M = Mut;
+        type T = T;
+    }
+
+    unsafe impl<T: ?Sized> IsReadPtr for *mut T {}
+    unsafe impl<T: ?Sized> IsReadPtr for *const T {}
+    unsafe impl<T: ?Sized> IsReadPtr for core::ptr::NonNull<T> {}
+    unsafe impl<T: ?Sized> IsReadPtr for &T {}
+    unsafe impl<T: ?Sized> IsReadPtr for &mut T {}
+
+    // Store a const pointer to do the manipulations with.
+    #[repr(transparent)]
+    pub struct Pointer<M: Mutability, T: ?Sized>(*const T, PhantomData<(M, M::Var<T>)>);
+
+    impl<M: Mutability, T: ?Sized> Clone for Pointer<M, T> {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+    impl<M: Mutability, T: ?Sized> Copy for Pointer<M, T> {}
+
+    #[inline(always)]
+    pub const fn new_pointer<P: IsPtr>(ptr: P) -> Pointer<P::M, P::T> {
+        // Safety
+        // `IsPtr` guarantees that `P` may be transmuted into `*const P::T`.
+        unsafe { Pointer(transmute_unchecked::<P, *const P::T>(ptr), PhantomData) }
+    }
+
+    /// Wraps the base of the accesses, which may also be a reference.
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const fn new_base_pointer<P: IsBasePtr>(ptr: P) -> Pointer<P::M, P::T> {
+        // Safety
+        // `IsBasePtr` guarantees that `P` may be transmuted into `*const P::T`.
+        unsafe { Pointer(transmute_unchecked::<P, *const P::T>(ptr), PhantomData) }
+    }
+
+    /// Wraps a pointer that was read in the middle of the accesses again.
+    /// This is the same as [`new_base_pointer()`], except for the error message.
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const fn new_read_pointer<P: IsReadPtr>(ptr: P) -> Pointer<P::M, P::T> {
+        new_base_pointer(ptr)
+    }
+
+    impl<M: Mutability, T: ?Sized> Pointer<M, T> {
+        /// Copies the address and type of a pointer to this pointer, keeping
+        /// mutability intact.
+        ///
+        /// # Safety
+        /// * `ptr` must be within the same allocated object as `self`.
+        #[inline(always)]
+        pub const unsafe fn copy_addr<E: ?Sized>(self, ptr: *const E) -> Pointer<M, E> {
+            Pointer(ptr, PhantomData)
+        }
+        /// Returns the inner pointer type.
+        #[inline(always)]
+        pub const fn into_inner(self) -> M::Raw<T> {
+            // Safety
+            // `Pointer<M, T>` can only be created with from a `P: IsPtr` or `P: IsBasePtr`,
+            // which both guarantee that `*const T` may be cast to `M::Raw<T>`.
+            unsafe { transmute_unchecked(self.0) }
+        }
+        /// Returns a `*const T` that points to the same place as this pointer.
+        #[inline(always)]
+        pub const fn into_const(self) -> *const T {
+            self.0
+        }
+        /// Returns a `*mut T` that points to the same place as this pointer.
+        ///
+        /// The provenance of the pointer is kept as is, so this can only be written through
+        /// if this pointer could be. Field accesses are projected from this with `addr_of_mut!`,
+        /// so that the resulting pointer is derived from a `*mut T` whenever the base was one.
+        #[inline(always)]
+        pub const fn into_mut(self) -> *mut T {
+            self.0.cast_mut()
+        }
+        /// Casts this pointer to another type.
+        #[inline(always)]
+        pub const fn cast<U>(self) -> Pointer<M, U> {
+            Pointer(self.0.cast(), PhantomData)
+        }
+        /// Changes the mutability of this pointer to [`Const`], without changing the type.
+        ///
+        /// This function mirrors [`pointer::cast_const()`], and is always fine to use.
+        ///
+        /// [`pointer::cast_const()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.cast_const
+        #[inline(always)]
+        pub const fn cast_const(self) -> Pointer<Const, T> {
+            Pointer(self.0, PhantomData)
+        }
+        /// Changes the mutability of this pointer to [`Mut`], without changing the type.
+        ///
+        /// This function mirrors [`pointer::cast_mut()`]. Calling it is safe, but writing
+        /// through the result asserts that the pointee may be mutated through this pointer,
+        /// so it should only be used on pointers that were originally mutable.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// let mut value = 5u32;
+        /// let ptr = new_pointer(&raw mut value);
+        ///
+        /// let read_only = ptr.cast_const();
+        /// let ptr = read_only.cast_mut();
+        /// assert_eq!(read_only.into_inner(), ptr.into_inner().cast_const());
+        ///
+        /// unsafe { ptr.into_inner().write(6) };
+        /// assert_eq!(value, 6);
+        /// ```
+        ///
+        /// [`pointer::cast_mut()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.cast_mut
+        #[inline(always)]
+        pub const fn cast_mut(self) -> Pointer<Mut, T> {
+            Pointer(self.0, PhantomData)
+        }
+        /// Casts this pointer to a dynamically sized type with `cast`, returning `None` if the
+        /// result does not cover exactly the same bytes, or is not aligned for `U`.
+        ///
+        /// Pointers to dynamically sized types can't be cast generically, so `cast` does the
+        /// actual cast, like `|ptr| ptr as *const Packet`. The sizes are compared with
+        /// [`RawLayout`], so no memory is read. This is useful for viewing a `[u8]` as a
+        /// custom dynamically sized type, where getting the length of the tail wrong would
+        /// make the new pointer cover more or less memory than the old one.
+        ///
+        /// The layout of `U` must still be compatible with the bytes, and reading through the
+        /// result still needs those bytes to be a valid `U`.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::{new_pointer, RawLayout}};
+        /// use std::ptr;
+        ///
+        /// #[repr(C)]
+        /// struct Packet {
+        ///     kind: u16,
+        ///     data: [u8],
+        /// }
+        ///
+        /// unsafe impl RawLayout for Packet {
+        ///     const ALIGN: usize = 2;
+        ///     fn size_of_raw(ptr: *const Self) -> usize {
+        ///         // 2 bytes for `kind`, then the data, rounded up to the alignment.
+        ///         (2 + (ptr as *const [u8]).len()).next_multiple_of(2)
+        ///     }
+        /// }
+        ///
+        /// #[repr(C, align(2))]
+        /// struct Buffer([u8; 6]);
+        ///
+        /// let buffer = Buffer([7, 0, 1, 2, 3, 4]);
+        /// let bytes = new_pointer(&buffer.0 as *const [u8]);
+        ///
+        /// // the data is the 4 bytes after `kind`.
+        /// let packet = bytes
+        ///     .reinterpret_dst(|ptr| ptr::slice_from_raw_parts(ptr.cast::<u8>(), 4) as *const Packet)
+        ///     .unwrap()
+        ///     .into_inner();
+        /// assert_eq!(unsafe { element_ptr!(packet => .data[3].*) }, 4);
+        ///
+        /// // using the length of all 6 bytes for the data would go past the end.
+        /// let too_long = bytes.reinterpret_dst(|ptr| ptr as *const Packet);
+        /// assert!(too_long.is_none());
+        /// ```
+        #[inline(always)]
+        pub fn reinterpret_dst<U: RawLayout + ?Sized>(
+            self,
+            cast: impl FnOnce(*const T) -> *const U,
+        ) -> Option<Pointer<M, U>>
+        where
+            T: RawLayout,
+        {
+            let ptr = cast(self.0);
+            if U::size_of_raw(ptr) == T::size_of_raw(self.0)
+                && ptr.cast::<u8>().addr() % U::ALIGN == 0
+            {
+                Some(Pointer(ptr, PhantomData))
+            } else {
+                None
+            }
+        }
+        /// Casts this pointer to a `U` and reads it, regardless of the type of the pointee.
+        ///
+        /// This is the same as <code>self.[cast::\<U>()](Self::cast).[read()](Pointer::read)</code>,
+        /// and is useful for reading a value as a different type, like the bits of a float.
+        ///
+        /// # Safety
+        /// All of the requirements of [`pointer::read()`] must be upheld as if the pointer
+        /// pointed to a `U`. Notably, it must be aligned for `U`, and the bytes must be
+        /// initialized and a valid `U`.
+        ///
+        /// [`pointer::read()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// let value = 1.0f32;
+        /// let bits = unsafe { new_pointer(&raw const value).cast_read::<u32>() };
+        /// assert_eq!(bits, 0x3F80_0000);
+        /// ```
+        #[inline(always)]
+        pub const unsafe fn cast_read<U>(self) -> U {
+            self.0.cast::<U>().read()
+        }
+        /// Casts this pointer to a `U` and reads it, checking that it is aligned for `U`
+        /// when debug assertions are enabled.
+        ///
+        /// This is the same as [`cast_read::<U>()`](Self::cast_read), but documents the
+        /// assumption that the pointer is aligned for `U`. This is mostly useful for reading
+        /// out of byte buffers, where the type of the pointer says nothing about its alignment.
+        ///
+        /// # Safety
+        /// All of the requirements of [`pointer::read()`] must be upheld as if the pointer
+        /// pointed to a `U`. The alignment check is only a debugging aid, so the pointer
+        /// must still be aligned when debug assertions are disabled.
+        ///
+        /// [`pointer::read()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// #[repr(C, align(4))]
+        /// struct Buffer([u8; 8]);
+        ///
+        /// let buffer = Buffer([1, 0, 0, 0, 2, 0, 0, 0]);
+        /// let ptr = new_pointer(buffer.0.as_ptr());
+        ///
+        /// let second = unsafe { ptr.add(4).read_aligned_as::<u32>() };
+        /// assert_eq!(second, u32::from_le_bytes([2, 0, 0, 0]));
+        /// ```
+        ///
+        /// A misaligned pointer panics when debug assertions are enabled.
+        ///
+        #[cfg_attr(debug_assertions, doc = "```should_panic")]
+        #[cfg_attr(not(debug_assertions), doc = "```ignore")]
+        /// # use element_ptr::helper::new_pointer;
+        /// # #[repr(C, align(4))]
+        /// # struct Buffer([u8; 8]);
+        /// let buffer = Buffer([0; 8]);
+        /// let ptr = new_pointer(buffer.0.as_ptr());
+        ///
+        /// let value = unsafe { ptr.add(1).read_aligned_as::<u32>() };
+        /// ```
+        #[inline(always)]
+        pub unsafe fn read_aligned_as<U>(self) -> U {
+            debug_assert!(
+                self.0.cast::<U>().is_aligned(),
+                "pointer is not aligned for the type being read"
+            );
+            self.0.cast::<U>().read()
+        }
+        /// Reads `N` bytes starting at this pointer, regardless of the type of the pointee.
+        ///
+        /// Each byte is read with [`pointer::read_volatile()`], one at a time
+        /// in increasing address order.
+        ///
+        /// # Safety
+        /// * The pointer must be valid for reads of `N` bytes.
+        /// * Each of the bytes must be initialized.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        ///
+        /// #[repr(C)]
+        /// struct Registers {
+        ///     id: u16,
+        ///     status: u32,
+        /// }
+        ///
+        /// let regs = Registers { id: 0x1234, status: 0xAABBCCDD };
+        /// let status = new_pointer(unsafe { element_ptr!(&regs => .status) });
+        ///
+        /// let bytes = unsafe { status.read_volatile_bytes::<4>() };
+        /// assert_eq!(bytes, 0xAABBCCDDu32.to_ne_bytes());
+        ///
+        /// // the pointee type does not limit how many bytes are read.
+        /// let bytes = unsafe { new_pointer(&raw const regs).read_volatile_bytes::<2>() };
+        /// assert_eq!(bytes, 0x1234u16.to_ne_bytes());
+        /// ```
+        ///
+        /// [`pointer::read_volatile()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read_volatile
+        #[inline(always)]
+        pub unsafe fn read_volatile_bytes<const N: usize>(self) -> [u8; N] {
+            let base = self.0.cast::<u8>();
+            let mut bytes = [0; N];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = base.add(i).read_volatile();
+            }
+            bytes
+        }
+        /// Copies a value described by `layout` from behind this pointer into the start of `dst`,
+        /// regardless of the type of the pointee.
+        ///
+        /// This is useful for values whose type is only known at runtime, such as in an interpreter.
+        /// Exactly `layout.size()` bytes are copied, and the rest of `dst` is left as it was.
+        ///
+        /// # Safety
+        /// * The pointer must be valid for reads of `layout.size()` bytes.
+        /// * Each of the bytes must be initialized.
+        /// * The bytes must not overlap with `dst`.
+        ///
+        /// # Panics
+        /// Panics if `dst` is shorter than `layout.size()`, or if the pointer is not aligned
+        /// to `layout.align()`.
+        ///
+        /// # Examples
+        /// ```
+        /// use core::alloc::Layout;
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// let values = [1u32, 2, 3, 4];
+        /// let ptr = new_pointer(values.as_ptr());
+        ///
+        /// // the layout of a `[u32; count]`, where `count` is only known at runtime.
+        /// let count = std::hint::black_box(3);
+        /// let layout = Layout::array::<u32>(count).unwrap();
+        ///
+        /// let mut dst = [0xFFu8; 16];
+        /// unsafe { ptr.read_dynamic(layout, &mut dst) };
+        ///
+        /// assert_eq!(dst[..12], *unsafe { values[..3].align_to::<u8>().1 });
+        /// assert_eq!(dst[12..], [0xFF; 4]);
+        /// ```
+        #[inline(always)]
+        pub unsafe fn read_dynamic(self, layout: Layout, dst: &mut [u8]) {
+            assert!(
+                dst.len() >= layout.size(),
+                "destination is too short for the layout"
+            );
+            assert!(
+                self.addr() & (layout.align() - 1) == 0,
+                "pointer is not aligned for the layout"
+            );
+            self.0
+                .cast::<u8>()
+                .copy_to_nonoverlapping(dst.as_mut_ptr(), layout.size());
+        }
+        /// Reads a `U` that starts `byte_offset` bytes after this pointer, regardless of
+        /// the type of the pointee.
+        ///
+        /// The value is read with [`pointer::read_unaligned()`], so the resulting address
+        /// does not need to be aligned for `U`. This makes it suitable for reading the fields
+        /// of packed structures out of an opaque buffer.
+        ///
+        /// # Safety
+        /// * All of the [requirements][offsetreq] for [`pointer::byte_add()`] must be upheld.
+        /// * All of the requirements of [`pointer::read_unaligned()`] must be upheld
+        ///   for the resulting pointer.
+        ///
+        /// [offsetreq]: https://doc.rust-lang.org/core/primitive.pointer.html#safety-2
+        /// [`pointer::byte_add()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.byte_add
+        /// [`pointer::read_unaligned()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read_unaligned
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// // a packed `{ tag: u8, len: u32, id: u64, flags: u8, end: u32 }`
+        /// let mut buf = [0u8; 18];
+        /// buf[0] = 7;
+        /// buf[1..5].copy_from_slice(&0xDEAD_BEEFu32.to_ne_bytes());
+        /// buf[5..13].copy_from_slice(&0x0123_4567_89AB_CDEFu64.to_ne_bytes());
+        /// buf[13] = 1;
+        /// buf[14..18].copy_from_slice(&42u32.to_ne_bytes());
+        ///
+        /// let ptr = new_pointer(buf.as_ptr());
+        /// unsafe {
+        ///     assert_eq!(ptr.read_packed::<u8>(0), 7);
+        ///     assert_eq!(ptr.read_packed::<u32>(1), 0xDEAD_BEEF);
+        ///     assert_eq!(ptr.read_packed::<u64>(5), 0x0123_4567_89AB_CDEF);
+        ///     assert_eq!(ptr.read_packed::<u8>(13), 1);
+        ///     assert_eq!(ptr.read_packed::<u32>(14), 42);
+        /// }
+        /// ```
+        #[inline(always)]
+        pub const unsafe fn read_packed<U>(self, byte_offset: usize) -> U {
+            self.0.cast::<U>().byte_add(byte_offset).read_unaligned()
+        }
+        /// Reads a length of type `L` from this pointer, and returns a slice pointer
+        /// of that many `E`s that follows it.
+        ///
+        /// The length and data are laid out as if they were in the struct
+        ///
+        #[cfg_attr(doctest, doc = "````notest")] // don't doctest this.
+        /// ```
+        /// #[repr(C)]
+        /// struct Prefixed {
+        ///     len: L,
+        ///     data: [E],
+        /// }
+        /// ````
+        /// so the data starts right after the length, rounded up to the alignment of `E`.
+        ///
+        /// # Safety
+        /// * All of the requirements of [`pointer::read()`] must be upheld for the length.
+        /// * The start of the data must be in the same allocated object as the length.
+        ///
+        /// # Panics
+        /// Panics if the length does not fit in a `usize`.
+        ///
+        /// [`pointer::read()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read
+        #[inline(always)]
+        pub unsafe fn prefixed_slice<L: TryInto<usize>, E>(self) -> Pointer<M, [E]> {
+            let len = match self.0.cast::<L>().read().try_into() {
+                Ok(len) => len,
+                Err(..) => panic!("slice length does not fit in a `usize`"),
+            };
+            let align = core::mem::align_of::<E>();
+            // round the end of the length up to the alignment of the data.
+            let offset = (core::mem::size_of::<L>() + align - 1) & !(align - 1);
+            self.cast::<u8>().byte_add(offset).as_slice_len(len)
+        }
+        /// Reads the length of a type-length-value record that starts at this pointer,
+        /// and returns a pointer to the record right after it.
+        ///
+        /// The length is an `L` at `len_offset` bytes into the record, and is read unaligned,
+        /// since records in these formats usually aren't aligned. It counts only the bytes of
+        /// the value, which starts `header_size` bytes into the record, so this moves the pointer
+        /// forward by `header_size + len` bytes. The length is in native byte order.
+        ///
+        /// # Safety
+        /// * All of the requirements of [`pointer::read_unaligned()`] must be upheld for the length.
+        /// * The end of the record must be in the same allocated object as this pointer.
+        ///
+        /// # Panics
+        /// Panics if the length does not fit in a `usize`, or if `header_size + len` overflows
+        /// a `usize`.
+        ///
+        /// [`pointer::read_unaligned()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read_unaligned
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// // each record is a one byte tag, a `u16` length, and then the value.
+        /// let mut buf = Vec::new();
+        /// for (tag, value) in [(1u8, &b"abc"[..]), (2, b""), (3, b"hello")] {
+        ///     buf.push(tag);
+        ///     buf.extend_from_slice(&(value.len() as u16).to_ne_bytes());
+        ///     buf.extend_from_slice(value);
+        /// }
+        ///
+        /// let end = buf.as_ptr_range().end;
+        /// let mut record = new_pointer(buf.as_ptr());
+        /// let mut tags = Vec::new();
+        /// while record.into_inner() != end {
+        ///     tags.push(unsafe { record.read() });
+        ///     record = unsafe { record.skip_tlv::<u16>(1, 3) };
+        /// }
+        ///
+        /// assert_eq!(tags, [1, 2, 3]);
+        /// ```
+        ///
+        /// A length that would move the pointer past the end of the address space panics.
+        ///
+        /// ```should_panic
+        /// # use element_ptr::helper::new_pointer;
+        /// let mut record = vec![1u8];
+        /// record.extend_from_slice(&usize::MAX.to_ne_bytes());
+        ///
+        /// let record = new_pointer(record.as_ptr());
+        /// unsafe { record.skip_tlv::<usize>(1, 9) };
+        /// ```
+        #[inline(always)]
+        pub unsafe fn skip_tlv<L: TryInto<usize>>(
+            mut self,
+            len_offset: usize,
+            header_size: usize,
+        ) -> Self {
+            let len = match self.read_packed::<L>(len_offset).try_into() {
+                Ok(len) => len,
+                Err(..) => panic!("record length does not fit in a `usize`"),
+            };
+            let Some(size) = header_size.checked_add(len) else {
+                panic!("record size does not fit in a `usize`");
+            };
+            self.0 = self.0.byte_add(size);
+            self
+        }
+        /// Returns this pointer unchanged.
+        ///
+        /// This is useful in code that is generic over [`Mutability`], to explicitly mark
+        /// the point where a copied pointer starts being used, such as after [`copy_addr()`](Self::copy_addr).
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::{new_pointer, Mutability, Pointer};
+        ///
+        /// unsafe fn sum<M: Mutability>(values: Pointer<M, u32>, len: usize) -> u32 {
+        ///     let mut total = 0;
+        ///     for i in 0..len {
+        ///         total += unsafe { values.reborrow().add(i).read() };
+        ///     }
+        ///     total
+        /// }
+        ///
+        /// let mut values = [1u32, 2, 3];
+        /// let ptr = new_pointer(values.as_mut_ptr());
+        ///
+        /// assert_eq!(ptr.reborrow().into_inner(), ptr.into_inner());
+        /// assert_eq!(unsafe { sum(ptr, 3) }, 6);
+        /// assert_eq!(unsafe { sum(ptr.cast_const(), 2) }, 3);
+        /// ```
+        #[inline(always)]
+        pub const fn reborrow(self) -> Self {
+            self
+        }
+        /// Creates a slice pointer of `count` `U`s that starts at this pointer.
+        ///
+        /// This does not read any memory. To use the slice, the pointer must be aligned for `U`
+        /// and the whole slice must be within the bounds of the same allocated object.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        ///
+        /// #[repr(C)]
+        /// struct Samples {
+        ///     count: u32,
+        ///     data: [u16; 8],
+        /// }
+        ///
+        /// let samples = Samples { count: 3, data: [5, 6, 7, 0, 0, 0, 0, 0] };
+        /// let data = new_pointer(unsafe { element_ptr!(&samples => .data) });
+        ///
+        /// let used = data.as_slice_len::<u16>(samples.count as usize);
+        /// assert_eq!(used.len(), 3);
+        /// assert_eq!(unsafe { used.as_slice() }, [5, 6, 7]);
+        /// ```
+        #[inline(always)]
+        pub const fn as_slice_len<U>(self, count: usize) -> Pointer<M, [U]> {
+            Pointer(
+                core::ptr::slice_from_raw_parts(self.0.cast::<U>(), count),
+                PhantomData,
+            )
+        }
+        /// Creates a byte slice pointer of `byte_len` bytes that starts at this pointer.
+        ///
+        /// This is the same as [`as_slice_len::<u8>()`](Self::as_slice_len).
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// let value = 0x0102_0304u32;
+        /// let bytes = new_pointer(&raw const value).as_byte_slice_len(4);
+        ///
+        /// assert_eq!(bytes.len(), 4);
+        /// assert_eq!(unsafe { bytes.as_slice() }, value.to_ne_bytes());
+        /// ```
+        #[inline(always)]
+        pub const fn as_byte_slice_len(self, byte_len: usize) -> Pointer<M, [u8]> {
+            self.as_slice_len(byte_len)
+        }
+        /// Creates a shared reference to the pointee, which can not outlive `_guard`.
+        ///
+        /// This is like [`pointer::as_ref()`], except that the lifetime of the reference is
+        /// bounded by a borrow of `_guard` instead of being chosen freely. Passing something
+        /// like a lock guard as `_guard` makes the compiler check that the reference is
+        /// not used after the lock is released.
+        ///
+        /// # Safety
+        /// * The pointer must be [convertible to a reference].
+        /// * The pointee must not be mutated through any other pointer while the reference
+        ///   is alive, unless it is inside of an [`UnsafeCell`].
+        ///
+        /// [`pointer::as_ref()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.as_ref
+        /// [convertible to a reference]: https://doc.rust-lang.org/core/ptr/index.html#pointer-to-reference-conversion
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        /// use std::sync::Mutex;
+        ///
+        /// struct Shared {
+        ///     lock: Mutex<()>,
+        ///     // only accessed while `lock` is held.
+        ///     value: u32,
+        /// }
+        ///
+        /// let shared = Shared { lock: Mutex::new(()), value: 5 };
+        /// let ptr = &shared as *const Shared;
+        ///
+        /// let guard = shared.lock.lock().unwrap();
+        /// let value = unsafe { new_pointer(element_ptr!(ptr => .value)).as_ref_scoped(&guard) };
+        /// assert_eq!(*value, 5);
+        /// drop(guard);
+        /// ```
+        ///
+        /// The reference can not be used after the guard is gone.
+        ///
+        /// ```compile_fail
+        /// # use element_ptr::{element_ptr, helper::new_pointer};
+        /// # use std::sync::Mutex;
+        /// # struct Shared {
+        /// #     lock: Mutex<()>,
+        /// #     value: u32,
+        /// # }
+        /// # let shared = Shared { lock: Mutex::new(()), value: 5 };
+        /// # let ptr = &shared as *const Shared;
+        /// let guard = shared.lock.lock().unwrap();
+        /// let value = unsafe { new_pointer(element_ptr!(ptr => .value)).as_ref_scoped(&guard) };
+        /// drop(guard);
+        /// assert_eq!(*value, 5);
+        /// ```
+        #[inline(always)]
+        pub const unsafe fn as_ref_scoped<G: ?Sized>(self, _guard: &G) -> &T {
+            &*self.0
+        }
+        /// Erases the type of this pointer, keeping mutability intact.
+        ///
+        /// This is useful for storing differently typed pointers together.
+        /// The original type can be restored with [`unerase()`](Pointer::unerase).
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// let mut count = 3u32;
+        /// let mut name = "spin";
+        ///
+        /// let erased = [
+        ///     new_pointer(&raw mut count).erase(),
+        ///     new_pointer(&raw mut name).erase(),
+        /// ];
+        ///
+        /// unsafe {
+        ///     erased[0].unerase::<u32>().write(4);
+        ///     assert_eq!(erased[1].unerase::<&str>().read(), "spin");
+        /// }
+        /// assert_eq!(count, 4);
+        /// ```
+        #[inline(always)]
+        pub const fn erase(self) -> Pointer<M, ()> {
+            self.cast()
+        }
+        /// Returns the address of this pointer.
+        ///
+        /// This function is a wrapper around [`pointer::addr()`], so it does not
+        /// expose the provenance of the pointer.
+        ///
+        /// [`pointer::addr()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.addr
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        ///
+        /// #[repr(C)]
+        /// struct Pair {
+        ///     first: u32,
+        ///     second: u32,
+        /// }
+        ///
+        /// let pair = Pair { first: 1, second: 2 };
+        /// let base = &raw const pair;
+        ///
+        /// let first = new_pointer(unsafe { element_ptr!(base => .first) });
+        /// let second = new_pointer(unsafe { element_ptr!(base => .second) });
+        /// assert_eq!(first.addr(), base.addr());
+        /// assert_eq!(second.addr() - first.addr(), 4);
+        /// ```
+        #[inline(always)]
+        pub fn addr(self) -> usize {
+            self.0.addr()
+        }
+        /// Returns whether this pointer is null.
+        ///
+        /// This function is a wrapper around [`pointer::is_null()`]. It is always false
+        /// for a pointer that came from a [`NonNull<T>`](core::ptr::NonNull).
+        ///
+        /// [`pointer::is_null()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.is_null
+        #[inline(always)]
+        pub fn is_null(self) -> bool {
+            self.0.is_null()
+        }
+        /// Returns a key that orders pointers by their address, such as for a [`BTreeMap`].
+        ///
+        /// This is the same as [`addr()`](Self::addr), so it ignores the provenance and type
+        /// of the pointer. That lets pointers of different types be keyed in the same map.
+        ///
+        /// [`BTreeMap`]: https://doc.rust-lang.org/std/collections/struct.BTreeMap.html
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        /// use std::collections::BTreeMap;
+        ///
+        /// #[repr(C)]
+        /// struct Device {
+        ///     id: u32,
+        ///     name: &'static str,
+        /// }
+        ///
+        /// let device = Device { id: 7, name: "timer" };
+        /// let ptr = &device as *const Device;
+        ///
+        /// // pointers of different types, in the same map.
+        /// let mut fields = BTreeMap::new();
+        /// unsafe {
+        ///     fields.insert(new_pointer(element_ptr!(ptr => .name)).as_ord_key(), "name");
+        ///     fields.insert(new_pointer(element_ptr!(ptr => .id)).as_ord_key(), "id");
+        /// }
+        ///
+        /// // the keys are ordered by address, so the fields come out in declaration order.
+        /// assert_eq!(fields.values().copied().collect::<Vec<_>>(), ["id", "name"]);
+        /// assert_eq!(fields[&new_pointer(ptr).as_ord_key()], "id");
+        /// ```
+        #[inline(always)]
+        pub fn as_ord_key(self) -> usize {
+            self.addr()
+        }
+        /// Creates a pointer with the address of `addr_src`, but the type and provenance
+        /// of this pointer.
+        ///
+        /// This function is a wrapper around [`pointer::with_addr()`], which is useful for
+        /// recombining an address that was cleaned up (such as removing a tag) with its type.
+        /// The resulting pointer is only valid to use if the address is in the bounds
+        /// of the allocated object that this pointer came from.
+        ///
+        /// [`pointer::with_addr()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.with_addr
+        ///
+        /// # Safety
+        /// If this is a `NonNull` pointer, `addr_src` must not be null.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// let values = [1u32, 2, 3];
+        /// let typed = new_pointer(values.as_ptr());
+        ///
+        /// // the low bit of an aligned pointer can be used as a tag.
+        /// let tagged = unsafe { typed.add(2).cast::<u8>().map_addr(|addr| addr | 1) };
+        /// let untagged = unsafe { tagged.map_addr(|addr| addr & !1) };
+        ///
+        /// let third = unsafe { typed.with_addr_of(untagged) };
+        /// assert_eq!(third.addr(), untagged.addr());
+        /// assert_eq!(unsafe { third.read() }, 3);
+        /// ```
+        #[inline(always)]
+        pub unsafe fn with_addr_of<U: ?Sized>(mut self, addr_src: Pointer<M, U>) -> Self {
+            self.0 = self.0.with_addr(addr_src.addr());
+            self
+        }
+        /// Creates a pointer with the address `addr`, but the type and provenance of this pointer.
+        ///
+        /// This function is a wrapper around [`pointer::with_addr()`]. The resulting pointer is
+        /// only valid to use if `addr` is in the bounds of the allocated object that this pointer
+        /// came from, which is up to the caller.
+        ///
+        /// [`pointer::with_addr()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.with_addr
+        ///
+        /// # Safety
+        /// If this is a `NonNull` pointer, `addr` must not be `0`.
+        #[inline(always)]
+        pub unsafe fn with_addr(mut self, addr: usize) -> Self {
+            self.0 = self.0.with_addr(addr);
+            self
+        }
+        /// Creates a pointer with the address that `f` returns for the address of this pointer,
+        /// keeping the type and provenance of this pointer.
+        ///
+        /// This function is a wrapper around [`pointer::map_addr()`], and is useful for things
+        /// like clearing the tag bits of a tagged pointer. The same rules as
+        /// [`with_addr()`](Self::with_addr) apply to the result.
+        ///
+        /// [`pointer::map_addr()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.map_addr
+        ///
+        /// # Safety
+        /// If this is a `NonNull` pointer, `f` must not return `0`.
+        #[inline(always)]
+        pub unsafe fn map_addr(mut self, f: impl FnOnce(usize) -> usize) -> Self {
+            self.0 = self.0.map_addr(f);
+            self
+        }
+        /// Rounds the address of this pointer up to the next multiple of `align`,
+        /// keeping the provenance intact.
+        ///
+        /// `align` must be a power of two. An address that would round past
+        /// `usize::MAX` wraps around to `0`.
+        ///
+        /// # Safety
+        /// If this is a `NonNull` pointer, the result must not be null.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// #[repr(align(8))]
+        /// struct Buffer([u8; 16]);
+        ///
+        /// let buffer = Buffer([0; 16]);
+        /// let start = new_pointer(buffer.0.as_ptr());
+        ///
+        /// unsafe {
+        ///     // an address that is already aligned stays the same.
+        ///     assert_eq!(start.align_up(8).into_inner(), start.into_inner());
+        ///     assert_eq!(start.add(8).align_up(8).into_inner(), start.add(8).into_inner());
+        ///     // anything else moves to the next multiple.
+        ///     assert_eq!(start.add(3).align_up(8).into_inner(), start.add(8).into_inner());
+        ///     assert_eq!(start.add(9).align_up(4).into_inner(), start.add(12).into_inner());
+        /// }
+        /// ```
+        #[inline(always)]
+        pub unsafe fn align_up(mut self, align: usize) -> Self {
+            debug_assert!(align.is_power_of_two(), "`align` must be a power of two");
+            let mask = align.wrapping_sub(1);
+            self.0 = self.0.map_addr(|addr| addr.wrapping_add(mask) & !mask);
+            self
+        }
+        /// Rounds the address of this pointer down to the previous multiple of `align`,
+        /// keeping the provenance intact.
+        ///
+        /// `align` must be a power of two. An address below `align` rounds down to `0`.
+        ///
+        /// # Safety
+        /// If this is a `NonNull` pointer, the result must not be null.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// #[repr(align(8))]
+        /// struct Buffer([u8; 16]);
+        ///
+        /// let buffer = Buffer([0; 16]);
+        /// let start = new_pointer(buffer.0.as_ptr());
+        ///
+        /// unsafe {
+        ///     // an address that is already aligned stays the same.
+        ///     assert_eq!(start.add(8).align_down(8).into_inner(), start.add(8).into_inner());
+        ///     // anything else moves to the previous multiple.
+        ///     assert_eq!(start.add(7).align_down(8).into_inner(), start.into_inner());
+        ///     assert_eq!(start.add(13).align_down(4).into_inner(), start.add(12).into_inner());
+        /// }
+        /// ```
+        #[inline(always)]
+        pub unsafe fn align_down(mut self, align: usize) -> Self {
+            debug_assert!(align.is_power_of_two(), "`align` must be a power of two");
+            let mask = align.wrapping_sub(1);
+            self.0 = self.0.map_addr(|addr| addr & !mask);
+            self
+        }
+        /// Checks that the address of this pointer is a multiple of `align`, when debug assertions
+        /// are enabled, and returns it unchanged.
+        ///
+        /// This is useful for documenting (and checking) alignment that the type system
+        /// doesn't know about, such as a field inside of a `#[repr(align(N))]` wrapper.
+        ///
+        /// `align` must be a power of two.
+        #[inline(always)]
+        pub fn debug_assert_aligned(self, align: usize) -> Self {
+            debug_assert!(align.is_power_of_two(), "`align` must be a power of two");
+            debug_assert!(
+                self.addr() & align.wrapping_sub(1) == 0,
+                "pointer is not aligned to {align} bytes"
+            );
+            self
+        }
+    }
+
+    impl<M: Mutability> Pointer<M, ()> {
+        /// Restores the type of a pointer that was erased with [`erase()`](Pointer::erase).
+        ///
+        /// This is just a cast, so `U` should be the type the pointer had before it was erased.
+        #[inline(always)]
+        pub const fn unerase<U>(self) -> Pointer<M, U> {
+            self.cast()
+        }
+    }
+
+    impl<M: Mutability, T> Pointer<M, T> {
+        /// Checks that this pointer is aligned for `T`, when debug assertions are enabled,
+        /// and returns it unchanged.
+        ///
+        /// This is what the `.{aligned}` access uses to check a `#[repr(align(N))]` wrapper
+        /// before going into its field.
+        #[inline(always)]
+        pub fn debug_assert_pointee_aligned(self) -> Self {
+            self.debug_assert_aligned(core::mem::align_of::<T>())
+        }
+        /// Calculates the offset of this pointer in units of `T`.
+        ///
+        /// This function is a wrapper around [`pointer::add()`].
+        ///
+        /// # Safety
+        /// * See [`pointer::add()`] for the safety requirements.
+        ///
+        /// [`pointer::add()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.add
+        #[inline(always)]
+        pub const unsafe fn add(mut self, count: usize) -> Self {
+            #[cfg(feature = "debug-checks")]
+            debug_check_count::<T>(count);
+            self.0 = self.0.add(count);
+            self
+        }
+        /// Calculates the offset of this pointer in units of `T`.
+        ///
+        /// This function is a wrapper around [`pointer::sub()`].
+        ///
+        /// # Safety
+        /// * See [`pointer::sub()`] for the safety requirements.
+        ///
+        /// [`pointer::sub()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.sub
+        #[inline(always)]
+        pub const unsafe fn sub(mut self, count: usize) -> Self {
+            #[cfg(feature = "debug-checks")]
+            debug_check_count::<T>(count);
+            self.0 = self.0.sub(count);
+            self
+        }
+        /// Calculates the offset of this pointer in units of `T`.
+        ///
+        /// This function is a wrapper around [`pointer::offset()`].
+        ///
+        /// # Safety
+        /// * See [`pointer::offset()`] for the safety requirements.
+        ///
+        /// [`pointer::offset()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.offset
+        #[inline(always)]
+        pub const unsafe fn offset(mut self, count: isize) -> Self {
+            #[cfg(feature = "debug-checks")]
+            debug_check_count::<T>(count.unsigned_abs());
+            self.0 = self.0.offset(count);
+            self
+        }
+        /// Calculates the offset of this pointer in units of `T`, returning `None` if
+        /// the address would overflow or be null.
+        ///
+        /// This is useful for offsets that come from untrusted data, such as relative pointers.
+        /// Only the address itself is checked, so the resulting pointer may still
+        /// be outside of the allocated object. It is calculated with [`pointer::wrapping_offset()`],
+        /// so creating it is always safe, but using it is only valid if it is in bounds.
+        ///
+        /// [`pointer::wrapping_offset()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.wrapping_offset
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// let high = new_pointer(core::ptr::without_provenance::<u16>(usize::MAX - 3));
+        /// assert_eq!(high.checked_offset(1).map(|p| p.addr()), Some(usize::MAX - 1));
+        /// assert!(high.checked_offset(2).is_none());
+        ///
+        /// let low = new_pointer(core::ptr::without_provenance::<u16>(4));
+        /// assert_eq!(low.checked_offset(-1).map(|p| p.addr()), Some(2));
+        /// assert!(low.checked_offset(-2).is_none());
+        /// assert!(low.checked_offset(-3).is_none());
+        /// assert!(low.checked_offset(isize::MIN).is_none());
+        /// assert!(low.checked_offset(isize::MAX).is_none());
+        /// ```
+        #[inline(always)]
+        pub fn checked_offset(mut self, count: isize) -> Option<Self> {
+            let bytes = count.checked_mul(core::mem::size_of::<T>() as isize)?;
+            // a null address is rejected too, so that this never makes a null `NonNull`.
+            if self.addr().checked_add_signed(bytes)? == 0 {
+                return None;
+            }
+            self.0 = self.0.wrapping_byte_offset(bytes);
+            Some(self)
+        }
+        /// Calculates the offset of this pointer in bytes.
+        ///
+        /// This function is a wrapper around [`pointer::byte_add()`].
+        ///
+        /// # Safety
+        /// * See [`pointer::byte_add()`] for the safety requirements.
+        ///
+        /// [`pointer::byte_add()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.byte_add
+        #[inline(always)]
+        pub const unsafe fn byte_add(mut self, count: usize) -> Self {
+            #[cfg(feature = "debug-checks")]
+            debug_check_count::<u8>(count);
+            self.0 = self.0.byte_add(count);
+            self
+        }
+        /// Calculates the offset of this pointer in bytes.
+        ///
+        /// This function is a wrapper around [`pointer::byte_sub()`].
+        ///
+        /// # Safety
+        /// * See [`pointer::byte_sub()`] for the safety requirements.
+        ///
+        /// [`pointer::byte_sub()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.byte_sub
+        #[inline(always)]
+        pub const unsafe fn byte_sub(mut self, count: usize) -> Self {
+            #[cfg(feature = "debug-checks")]
+            debug_check_count::<u8>(count);
+            self.0 = self.0.byte_sub(count);
+            self
+        }
+        /// Calculates the offset of this pointer in bytes.
+        ///
+        /// This function is a wrapper around [`pointer::byte_offset()`].
+        ///
+        /// # Safety
+        /// * See [`pointer::byte_offset()`] for the safety requirements.
+        ///
+        /// [`pointer::byte_offset()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.byte_offset
+        #[inline(always)]
+        pub const unsafe fn byte_offset(mut self, count: isize) -> Self {
+            self.0 = self.0.byte_offset(count);
+            self
+        }
+        /// Calculates the offset of this pointer in units of `T` using wrapping arithmetic.
+        ///
+        /// This function is a wrapper around [`pointer::wrapping_add()`].
+        /// Unlike [`add()`](Self::add), the result may be outside of the allocated object,
+        /// but it must be back in bounds before it is used to access memory.
+        ///
+        /// [`pointer::wrapping_add()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.wrapping_add
+        ///
+        /// # Safety
+        /// If this is a `NonNull` pointer, the result must not be null.
+        #[inline(always)]
+        pub const unsafe fn wrapping_add(mut self, count: usize) -> Self {
+            self.0 = self.0.wrapping_add(count);
+            self
+        }
+        /// Calculates the offset of this pointer in units of `T` using wrapping arithmetic.
+        ///
+        /// This function is a wrapper around [`pointer::wrapping_sub()`].
+        /// Unlike [`sub()`](Self::sub), the result may be outside of the allocated object,
+        /// but it must be back in bounds before it is used to access memory.
+        ///
+        /// [`pointer::wrapping_sub()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.wrapping_sub
+        ///
+        /// # Safety
+        /// If this is a `NonNull` pointer, the result must not be null.
+        #[inline(always)]
+        pub const unsafe fn wrapping_sub(mut self, count: usize) -> Self {
+            self.0 = self.0.wrapping_sub(count);
+            self
+        }
+        /// Calculates the offset of this pointer in bytes using wrapping arithmetic.
+        ///
+        /// This function is a wrapper around [`pointer::wrapping_byte_add()`].
+        /// Unlike [`byte_add()`](Self::byte_add), the result may be outside of the allocated object,
+        /// but it must be back in bounds before it is used to access memory.
+        ///
+        /// [`pointer::wrapping_byte_add()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.wrapping_byte_add
+        ///
+        /// # Safety
+        /// If this is a `NonNull` pointer, the result must not be null.
+        #[inline(always)]
+        pub const unsafe fn wrapping_byte_add(mut self, count: usize) -> Self {
+            self.0 = self.0.wrapping_byte_add(count);
+            self
+        }
+        /// Calculates the offset of this pointer in bytes using wrapping arithmetic.
+        ///
+        /// This function is a wrapper around [`pointer::wrapping_byte_sub()`].
+        /// Unlike [`byte_sub()`](Self::byte_sub), the result may be outside of the allocated object,
+        /// but it must be back in bounds before it is used to access memory.
+        ///
+        /// [`pointer::wrapping_byte_sub()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.wrapping_byte_sub
+        ///
+        /// # Safety
+        /// If this is a `NonNull` pointer, the result must not be null.
+        #[inline(always)]
+        pub const unsafe fn wrapping_byte_sub(mut self, count: usize) -> Self {
+            self.0 = self.0.wrapping_byte_sub(count);
+            self
+        }
+        /// Calculates how many elements this pointer needs to move forward to be aligned to `align`.
+        ///
+        /// This function is a wrapper around [`pointer::align_offset()`], and like it,
+        /// returns `usize::MAX` if the pointer can't be aligned by moving it in units of `T`.
+        ///
+        /// # Panics
+        /// Panics if `align` is not a power of two.
+        ///
+        /// [`pointer::align_offset()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.align_offset
+        #[inline(always)]
+        pub fn align_offset(self, align: usize) -> usize {
+            self.0.align_offset(align)
+        }
+        /// Returns a pointer to a `U` that is `offset` bytes into the pointee.
+        ///
+        /// This is the same as [`byte_add()`](Self::byte_add) followed by [`cast()`](Self::cast),
+        /// and is useful for types that are only declared as an array of bytes, such as an opaque
+        /// FFI type where only a few fields matter. Inside of [`element_ptr!`](crate::element_ptr!)
+        /// this is written as `u8+ (offset) as U =>`.
+        ///
+        /// # Safety
+        /// * The same requirements as [`byte_add()`](Self::byte_add) apply.
+        /// * There must actually be a `U` at that offset to read or write it. Matching the layout
+        ///   of the real type, such as a C struct, is up to the caller.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// // struct stat_lite { uint32_t mode; uint32_t uid; uint64_t size; };
+        /// #[repr(C, align(8))]
+        /// struct StatLite([u8; 16]);
+        ///
+        /// const MODE: usize = 0;
+        /// const SIZE: usize = 8;
+        ///
+        /// let mut stat = StatLite([0; 16]);
+        /// let ptr = new_pointer(&raw mut stat);
+        ///
+        /// unsafe {
+        ///     ptr.field_at::<u32>(MODE).write(0o644);
+        ///     ptr.field_at::<u64>(SIZE).write(4096);
+        ///
+        ///     assert_eq!(ptr.field_at::<u32>(MODE).read(), 0o644);
+        ///     assert_eq!(ptr.field_at::<u64>(SIZE).read(), 4096);
+        /// }
+        /// assert_eq!(stat.0[8..], 4096u64.to_ne_bytes());
+        /// ```
+        #[inline(always)]
+        pub const unsafe fn field_at<U>(self, offset: usize) -> Pointer<M, U> {
+            self.byte_add(offset).cast()
+        }
+        /// Calculates the offset of this pointer in bytes from any integer type
+        /// that fits in an `isize`, such as an `i16` or `i32` read from a file format.
+        ///
+        /// This is the same as [`byte_offset()`](Self::byte_offset) after converting `count`.
+        ///
+        /// # Safety
+        /// * See [`byte_offset()`](Self::byte_offset) for the safety requirements.
+        ///
+        /// # Panics
+        /// Panics if `count` does not fit in an `isize`. This can only happen for
+        /// 64 bit integers on targets with a smaller pointer width.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// let bytes = [10u8, 20, 30, 40, 50, 60];
+        /// let middle = unsafe { new_pointer(bytes.as_ptr()).add(3) };
+        ///
+        /// unsafe {
+        ///     assert_eq!(middle.byte_offset_signed(-3i8).read(), 10);
+        ///     assert_eq!(middle.byte_offset_signed(-1i16).read(), 30);
+        ///     assert_eq!(middle.byte_offset_signed(2i32).read(), 60);
+        ///     assert_eq!(middle.byte_offset_signed(-2i64).read(), 20);
+        ///     assert_eq!(middle.byte_offset_signed(1u8).read(), 50);
+        ///     assert_eq!(middle.byte_offset_signed(0isize).read(), 40);
+        /// }
+        /// ```
+        #[inline(always)]
+        pub unsafe fn byte_offset_signed<I: TryInto<isize>>(self, count: I) -> Self {
+            match count.try_into() {
+                Ok(count) => self.byte_offset(count),
+                Err(..) => panic!("byte offset does not fit in an `isize`"),
+            }
+        }
+        /// Calculates the distance between this pointer and `origin` in units of `T`.
+        ///
+        /// This function is a wrapper around [`pointer::offset_from()`].
+        ///
+        /// # Safety
+        /// * See [`pointer::offset_from()`] for the safety requirements.
+        ///
+        /// [`pointer::offset_from()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.offset_from
+        #[inline(always)]
+        pub const unsafe fn offset_from<N: Mutability>(self, origin: Pointer<N, T>) -> isize {
+            self.0.offset_from(origin.0)
+        }
+        /// Calculates the distance between this pointer and `origin` in bytes.
+        ///
+        /// This function is a wrapper around [`pointer::byte_offset_from()`].
+        /// Unlike [`offset_from()`](Self::offset_from), the pointers may point to different types.
+        ///
+        /// # Safety
+        /// * See [`pointer::byte_offset_from()`] for the safety requirements.
+        ///
+        /// [`pointer::byte_offset_from()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.byte_offset_from
+        #[inline(always)]
+        pub const unsafe fn byte_offset_from<N: Mutability, U>(
+            self,
+            origin: Pointer<N, U>,
+        ) -> isize {
+            self.0.byte_offset_from(origin.0)
+        }
+        /// Reads the value from behind this pointer.
+        ///
+        /// This function is a wrapper around [`pointer::read()`].
+        ///
+        /// # Safety
+        /// * See [`pointer::read()`] for the safety requirements.
+        ///
+        /// [`pointer::read()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read
+        #[inline(always)]
+        pub const unsafe fn read(self) -> T {
+            self.0.read()
+        }
+        /// Returns an iterator over pointers to each element starting at this one, which stops
+        /// right before the first element that `is_end` returns true for.
+        ///
+        /// This is useful for arrays that end with a sentinel, such as the null terminated
+        /// `argv` array in C. Each element is read to check it, and the copy that `is_end` is
+        /// given is never dropped, so the sentinel is not yielded.
+        ///
+        /// # Safety
+        /// * There must be a sentinel in the same allocated object, after this pointer.
+        /// * Every element up to and including the sentinel must be valid to
+        ///   [`read()`](Self::read) while the iterator is used.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        /// use std::ffi::{c_char, CStr};
+        ///
+        /// let args = [c"ls".as_ptr(), c"-l".as_ptr(), c"/tmp".as_ptr(), std::ptr::null()];
+        /// let argv: *const *const c_char = args.as_ptr();
+        ///
+        /// let args: Vec<&CStr> = unsafe {
+        ///     new_pointer(argv)
+        ///         .iter_until_sentinel(|arg| arg.is_null())
+        ///         .map(|arg| CStr::from_ptr(arg.read()))
+        ///         .collect()
+        /// };
+        ///
+        /// assert_eq!(args, [c"ls", c"-l", c"/tmp"]);
+        /// ```
+        #[inline(always)]
+        pub unsafe fn iter_until_sentinel(
+            self,
+            is_end: impl Fn(&T) -> bool,
+        ) -> impl Iterator<Item = Self> {
+            let mut ptr = Some(self);
+            core::iter::from_fn(move || {
+                let current = ptr?;
+                if is_end(&ManuallyDrop::new(current.0.read())) {
+                    ptr = None;
+                    None
+                } else {
+                    ptr = Some(current.add(1));
+                    Some(current)
+                }
+            })
+        }
+        /// Reads the value from behind this pointer and compares it to `value`.
+        ///
+        /// This does not create a reference to the pointee, and the copy that is read is never
+        /// dropped, so it works for types that are not [`Copy`] too. This is useful for checking for
+        /// a sentinel or terminator while scanning through memory.
+        ///
+        /// # Safety
+        /// * The same requirements as [`read()`](Self::read) apply, so the pointee must be
+        ///   initialized and a valid `T`.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// const END: u16 = 0xFFFF;
+        ///
+        /// let ids = [4, 8, 15, 16, END, 23, 42];
+        /// let mut ptr = new_pointer(ids.as_ptr());
+        ///
+        /// let mut len = 0;
+        /// while unsafe { !ptr.reads_eq(&END) } {
+        ///     ptr = unsafe { ptr.add(1) };
+        ///     len += 1;
+        /// }
+        ///
+        /// assert_eq!(len, 4);
+        /// ```
+        #[inline(always)]
+        pub unsafe fn reads_eq(self, value: &T) -> bool
+        where
+            T: PartialEq,
+        {
+            *ManuallyDrop::new(self.0.read()) == *value
+        }
+        /// Copies the value from behind this pointer into `dst`, without creating an
+        /// intermediate copy of it on the stack.
+        ///
+        /// This is useful for large types, where [`read()`](Self::read) may overflow the stack.
+        /// `dst` will only be initialized if the pointee is initialized.
+        ///
+        /// A `.*` access in [`element_ptr!`](crate::element_ptr) reads the value into a temporary
+        /// first, and the compiler is not guaranteed to elide that copy when the value is then
+        /// moved somewhere else. This copies straight into `dst` instead, which matters for large
+        /// aggregates on targets with small stacks.
+        ///
+        /// # Safety
+        /// * See [`pointer::copy_to_nonoverlapping()`] for the safety requirements.
+        ///
+        /// [`pointer::copy_to_nonoverlapping()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.copy_to_nonoverlapping
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        /// use std::mem::MaybeUninit;
+        ///
+        /// struct Device {
+        ///     id: u32,
+        ///     firmware: [u8; 4096],
+        /// }
+        ///
+        /// let mut device = Box::new(Device { id: 3, firmware: [0; 4096] });
+        /// device.firmware[4095] = 0xAA;
+        /// let ptr = &*device as *const Device;
+        ///
+        /// let mut firmware = Box::new(MaybeUninit::<[u8; 4096]>::uninit());
+        /// let firmware = unsafe {
+        ///     new_pointer(element_ptr!(ptr => .firmware)).read_into_uninit(&mut firmware);
+        ///     firmware.assume_init()
+        /// };
+        ///
+        /// assert_eq!(firmware[4095], 0xAA);
+        /// assert!(firmware[..4095].iter().all(|&byte| byte == 0));
+        /// ```
+        ///
+        /// The copy does not change when the pointee is changed later.
+        ///
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        /// use std::mem::MaybeUninit;
+        ///
+        /// let mut value = [1u64, 2];
+        /// let ptr = new_pointer(&raw mut value);
+        ///
+        /// let mut saved = MaybeUninit::uninit();
+        /// unsafe {
+        ///     ptr.read_into_uninit(&mut saved);
+        ///     ptr.write([3, 4]);
+        ///     assert_eq!(saved.assume_init(), [1, 2]);
+        /// }
+        /// assert_eq!(value, [3, 4]);
+        /// ```
+        #[inline(always)]
+        pub const unsafe fn read_into_uninit(self, dst: &mut MaybeUninit<T>) {
+            self.0.copy_to_nonoverlapping(dst.as_mut_ptr(), 1);
+        }
+        /// Reads the value from behind this pointer, wrapping it in a [`ManuallyDrop`].
+        ///
+        /// This should be used over [`read()`](Self::read) when the pointee is still
+        /// owned by something else, like when reading a non-[`Copy`] value out of memory
+        /// that will be dropped later. The returned value will not be dropped unless
+        /// it is explicitly taken out of the [`ManuallyDrop`], which would then make
+        /// it a duplicate of the pointee.
+        ///
+        /// # Safety
+        /// All of the requirements of [`pointer::read()`] must be upheld.
+        ///
+        /// [`pointer::read()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        ///
+        /// struct Entry {
+        ///     key: u32,
+        ///     name: String,
+        /// }
+        ///
+        /// let entry = Entry { key: 1, name: String::from("first") };
+        /// let name = new_pointer(unsafe { element_ptr!(&entry => .name) });
+        ///
+        /// // `entry` still owns the string, so the copy must not be dropped.
+        /// let copy = unsafe { name.read_manually_drop() };
+        /// assert_eq!(copy.as_str(), "first");
+        ///
+        /// drop(entry);
+        /// ```
+        #[inline(always)]
+        pub const unsafe fn read_manually_drop(self) -> ManuallyDrop<T> {
+            ManuallyDrop::new(self.0.read())
+        }
+        /// Reads a C style tagged union, where this pointer points to the tag.
+        ///
+        /// The tag is read and passed to `f` along with a pointer to the payload,
+        /// which `f` can then use to read the variant that the tag selects.
+        /// The payload is assumed to directly follow the tag, as if they were in the struct
+        ///
+        #[cfg_attr(doctest, doc = "````notest")] // don't doctest this.
+        /// ```
+        /// #[repr(C)]
+        /// struct Tagged {
+        ///     tag: T,
+        ///     payload: U,
+        /// }
+        /// ````
+        ///
+        /// # Safety
+        /// * All of the requirements of [`pointer::read()`] must be upheld for the tag.
+        /// * The payload must be in the same allocated object as the tag.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        ///
+        /// #[repr(C)]
+        /// union Payload {
+        ///     int: u32,
+        ///     float: f32,
+        /// }
+        ///
+        /// #[repr(C)]
+        /// struct Value {
+        ///     tag: u8,
+        ///     payload: Payload,
+        /// }
+        ///
+        /// #[derive(Debug, PartialEq)]
+        /// enum Number {
+        ///     Int(u32),
+        ///     Float(f32),
+        /// }
+        ///
+        /// fn read(value: &Value) -> Number {
+        ///     let tag = new_pointer(unsafe { element_ptr!(value => .tag) });
+        ///     unsafe {
+        ///         tag.read_tagged_union::<Payload, _>(|tag, payload| match tag {
+        ///             0 => Number::Int(element_ptr!(payload.into_inner() => .int.*)),
+        ///             _ => Number::Float(element_ptr!(payload.into_inner() => .float.*)),
+        ///         })
+        ///     }
+        /// }
+        ///
+        /// let int = Value { tag: 0, payload: Payload { int: 7 } };
+        /// let float = Value { tag: 1, payload: Payload { float: 0.5 } };
+        ///
+        /// assert_eq!(read(&int), Number::Int(7));
+        /// assert_eq!(read(&float), Number::Float(0.5));
+        /// ```
+        ///
+        /// [`pointer::read()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read
+        #[inline(always)]
+        pub unsafe fn read_tagged_union<U, R>(self, f: impl FnOnce(T, Pointer<M, U>) -> R) -> R {
+            let tag = self.0.read();
+            let align = core::mem::align_of::<U>();
+            // round the end of the tag up to the alignment of the payload.
+            let offset = (core::mem::size_of::<T>() + align - 1) & !(align - 1);
+            f(tag, self.cast::<u8>().byte_add(offset).cast())
+        }
+        /// Reads the value from behind this pointer without requiring it to be aligned.
+        ///
+        /// This function is a wrapper around [`pointer::read_unaligned()`].
+        /// Only the alignment requirement of [`read()`](Self::read) is dropped, so the value
+        /// must still be initialized and valid for `T`.
+        ///
+        /// # Safety
+        /// * See [`pointer::read_unaligned()`] for the safety requirements.
+        ///
+        /// [`pointer::read_unaligned()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read_unaligned
+        #[inline(always)]
+        pub const unsafe fn read_unaligned(self) -> T {
+            self.0.read_unaligned()
+        }
+        /// Reads the value from behind this pointer with a volatile read.
+        ///
+        /// This function is a wrapper around [`pointer::read_volatile()`].
+        ///
+        /// # Safety
+        /// * See [`pointer::read_volatile()`] for the safety requirements.
+        ///
+        /// [`pointer::read_volatile()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read_volatile
+        #[inline(always)]
+        pub unsafe fn read_volatile(self) -> T {
+            self.0.read_volatile()
+        }
+        /// Reads the value from behind this pointer with a volatile read,
+        /// and then issues a memory [`fence()`] with the ordering `order`.
+        ///
+        /// This is useful for MMIO sequences where the read must complete before
+        /// any memory accesses that come after it.
+        ///
+        /// # Safety
+        /// All of the requirements of [`pointer::read_volatile()`] must be upheld.
+        ///
+        /// # Panics
+        /// Panics if `order` is [`Relaxed`](Ordering::Relaxed).
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        /// use std::sync::atomic::Ordering;
+        ///
+        /// let status = 0x80u32;
+        /// let ptr = new_pointer(&raw const status);
+        ///
+        /// let ready = unsafe { ptr.read_volatile_then_fence(Ordering::Acquire) };
+        /// assert_eq!(ready, 0x80);
+        /// ```
+        ///
+        /// A [`Relaxed`](Ordering::Relaxed) fence does not order anything, so it panics.
+        ///
+        /// ```should_panic
+        /// # use element_ptr::helper::new_pointer;
+        /// # use std::sync::atomic::Ordering;
+        /// # let status = 0x80u32;
+        /// # let ptr = new_pointer(&raw const status);
+        /// unsafe { ptr.read_volatile_then_fence(Ordering::Relaxed) };
+        /// ```
+        ///
+        /// [`fence()`]: core::sync::atomic::fence
+        /// [`pointer::read_volatile()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read_volatile
+        #[inline(always)]
+        pub unsafe fn read_volatile_then_fence(self, order: Ordering) -> T {
+            let value = self.0.read_volatile();
+            core::sync::atomic::fence(order);
+            value
+        }
+        /// Reads the value from behind this pointer with a volatile read,
+        /// and then issues a [`compiler_fence()`] with the ordering `order`.
+        ///
+        /// This only keeps the compiler from moving memory accesses across the read, without
+        /// emitting any fence instructions. It is useful when the value is shared with an
+        /// interrupt handler or signal handler on the same core, where a full
+        /// [`read_volatile_then_fence()`](Self::read_volatile_then_fence) is not needed.
+        ///
+        /// # Safety
+        /// All of the requirements of [`pointer::read_volatile()`] must be upheld.
+        ///
+        /// # Panics
+        /// Panics if `order` is [`Relaxed`](Ordering::Relaxed).
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        /// use std::sync::atomic::Ordering;
+        ///
+        /// let pending = 1u8;
+        /// let ptr = new_pointer(&raw const pending);
+        ///
+        /// let value = unsafe { ptr.read_volatile_then_compiler_fence(Ordering::Acquire) };
+        /// assert_eq!(value, 1);
+        /// ```
+        ///
+        /// [`compiler_fence()`]: core::sync::atomic::compiler_fence
+        /// [`pointer::read_volatile()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read_volatile
+        #[inline(always)]
+        pub unsafe fn read_volatile_then_compiler_fence(self, order: Ordering) -> T {
+            let value = self.0.read_volatile();
+            core::sync::atomic::compiler_fence(order);
+            value
+        }
+        /// Repeatedly reads the value from behind this pointer with a volatile read
+        /// until `pred` returns true for it, such as when waiting on a status register.
+        ///
+        /// The value that `pred` accepted is returned as `Ok`. The value is read at most
+        /// `max_spins` times, and if none of those reads are accepted, `Err(())` is returned
+        /// instead, so the loop can never run forever. [`spin_loop()`](core::hint::spin_loop)
+        /// is called between reads.
+        ///
+        /// # Safety
+        /// All of the requirements of [`pointer::read_volatile()`] must be upheld
+        /// for every read.
+        ///
+        /// [`pointer::read_volatile()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read_volatile
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// let mut status = 0u32;
+        /// let raw = &raw mut status;
+        /// let ptr = new_pointer(raw);
+        ///
+        /// // pretend to be a device that counts up every time it is read.
+        /// let count_up = |status: u32| unsafe {
+        ///     raw.write_volatile(status + 1);
+        ///     status == 3
+        /// };
+        ///
+        /// // `3` is the fourth value that is read.
+        /// assert_eq!(unsafe { ptr.poll_until(count_up, 4) }, Ok(3));
+        ///
+        /// unsafe { raw.write(0) };
+        /// assert_eq!(unsafe { ptr.poll_until(count_up, 3) }, Err(()));
+        /// // it was only read three times.
+        /// assert_eq!(unsafe { raw.read() }, 3);
+        /// ```
+        // running out of spins is the only way this can fail, so there is nothing to report.
+        #[allow(clippy::result_unit_err)]
+        #[inline(always)]
+        pub unsafe fn poll_until(self, pred: impl Fn(T) -> bool, max_spins: usize) -> Result<T, ()>
+        where
+            T: Copy,
+        {
+            for spin in 0..max_spins {
+                if spin != 0 {
+                    core::hint::spin_loop();
+                }
+                let value = self.0.read_volatile();
+                if pred(value) {
+                    return Ok(value);
+                }
+            }
+            Err(())
+        }
+        /// Reads from this pointer with `f`, guarded by the sequence lock `seq`.
+        ///
+        /// This is the reading half of a seqlock, which is useful for data in shared memory
+        /// that another thread or process may be writing at any time. `f` is called until
+        /// the sequence number is even and does not change while `f` runs, which means no
+        /// write happened at the same time, and the result of that call is returned.
+        ///
+        /// The writer is expected to first increment the sequence number with
+        /// [`Relaxed`](Ordering::Relaxed) ordering, making it odd, followed by a
+        /// [`fence(Release)`](core::sync::atomic::fence). The fence keeps the data writes from
+        /// being moved above the increment, which a [`Release`](Ordering::Release) increment
+        /// alone would not. Then it writes the data, and increments the sequence number again
+        /// with [`Release`](Ordering::Release) ordering, making it even. There may only be one
+        /// writer at a time.
+        ///
+        /// # Safety
+        /// * `seq` must be valid for reads for the whole call.
+        /// * `f` may be called while the data is being written, so every read it does must be
+        ///   atomic (usually a [`Relaxed`](Ordering::Relaxed) load) to not be a data race.
+        ///   Volatile reads are not atomic, so they are only fine for memory that the Rust memory
+        ///   model doesn't cover, like memory shared with another process. `f` must not do
+        ///   anything with what it read besides return it, because the returned value is only
+        ///   known to be consistent after this function returns it.
+        /// * Any value read by `f` must be valid for its type, even if it is torn by a concurrent
+        ///   write. Plain integer data is always fine.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        /// use std::sync::atomic::{fence, AtomicU32, AtomicU64, Ordering};
+        ///
+        /// struct Shared {
+        ///     seq: AtomicU32,
+        ///     // these are always written to be the same value.
+        ///     a: AtomicU64,
+        ///     b: AtomicU64,
+        /// }
+        ///
+        /// struct SendPtr(*mut Shared);
+        /// unsafe impl Send for SendPtr {}
+        /// impl SendPtr {
+        ///     fn get(self) -> *mut Shared {
+        ///         self.0
+        ///     }
+        /// }
+        ///
+        /// let shared = Box::into_raw(Box::new(Shared {
+        ///     seq: AtomicU32::new(0),
+        ///     a: AtomicU64::new(0),
+        ///     b: AtomicU64::new(0),
+        /// }));
+        ///
+        /// let writer = SendPtr(shared);
+        /// let writer = std::thread::spawn(move || unsafe {
+        ///     let shared = writer.get();
+        ///     let shared = &*shared;
+        ///     for i in 1..=1000 {
+        ///         shared.seq.fetch_add(1, Ordering::Relaxed);
+        ///         fence(Ordering::Release);
+        ///         shared.a.store(i, Ordering::Relaxed);
+        ///         shared.b.store(i, Ordering::Relaxed);
+        ///         shared.seq.fetch_add(1, Ordering::Release);
+        ///     }
+        /// });
+        ///
+        /// let ptr = new_pointer(shared.cast_const());
+        /// let seq = new_pointer(unsafe { element_ptr!(shared => .seq) });
+        /// loop {
+        ///     let (a, b) = unsafe {
+        ///         ptr.read_seqlock(seq, |ptr| {
+        ///             let ptr = ptr.into_inner();
+        ///             let a = &*element_ptr!(ptr => .a);
+        ///             let b = &*element_ptr!(ptr => .b);
+        ///             (a.load(Ordering::Relaxed), b.load(Ordering::Relaxed))
+        ///         })
+        ///     };
+        ///     assert_eq!(a, b);
+        ///     if a == 1000 {
+        ///         break;
+        ///     }
+        /// }
+        ///
+        /// writer.join().unwrap();
+        /// drop(unsafe { Box::from_raw(shared) });
+        /// ```
+        pub unsafe fn read_seqlock<N: Mutability, R>(
+            self,
+            seq: Pointer<N, AtomicU32>,
+            mut f: impl FnMut(Self) -> R,
+        ) -> R {
+            let seq = &*seq.0;
+            loop {
+                let start = seq.load(Ordering::Acquire);
+                if start & 1 == 0 {
+                    let value = f(self);
+                    // keep the reads in `f` from being moved after the second load.
+                    core::sync::atomic::fence(Ordering::Acquire);
+                    if seq.load(Ordering::Relaxed) == start {
+                        return value;
+                    }
+                }
+                core::hint::spin_loop();
+            }
+        }
+        /// Reads the value from behind this pointer if it is non-null and aligned,
+        /// returning `None` otherwise.
+        ///
+        /// Nothing is read if either of the checks fail.
+        ///
+        /// # Safety
+        /// Besides being non-null and aligned, all of the requirements of [`pointer::read()`]
+        /// must still be upheld. Notably, there is no way to check that the pointee is initialized.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        /// use std::ptr;
+        ///
+        /// let values = [1u32, 2];
+        /// let ptr = new_pointer(values.as_ptr());
+        ///
+        /// unsafe {
+        ///     assert_eq!(ptr.add(1).try_read(), Some(2));
+        ///     assert_eq!(new_pointer(ptr::null::<u32>()).try_read(), None);
+        ///     // one byte in is not aligned for a `u32`.
+        ///     assert_eq!(ptr.byte_add(1).try_read(), None);
+        /// }
+        /// ```
+        ///
+        /// [`pointer::read()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read
+        #[inline(always)]
+        pub unsafe fn try_read(self) -> Option<T> {
+            if self.0.is_null() || !self.0.is_aligned() {
+                None
+            } else {
+                Some(self.0.read())
+            }
+        }
+    }
+
+    #[cfg(feature = "zerocopy")]
+    impl<M: Mutability, T: zerocopy::TryFromBytes> Pointer<M, T> {
+        /// Reads the value from behind this pointer, returning `None` if the bytes
+        /// are not a valid `T`, such as an enum with an unknown discriminant.
+        ///
+        /// This function is a wrapper around [`TryFromBytes::try_read_from_bytes()`].
+        /// Unlike [`read()`](Self::read), the pointee does not need to be a valid `T`,
+        /// so this can be used on untrusted memory.
+        ///
+        /// # Safety
+        /// * The pointer must be [valid] for reads of `size_of::<T>()` bytes.
+        ///   It does not need to be aligned.
+        /// * Every one of those bytes must be initialized. This means `T` must not
+        ///   contain any padding.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// let bytes = [0u8, 1];
+        /// let flags = new_pointer(bytes.as_ptr().cast::<bool>());
+        ///
+        /// unsafe {
+        ///     assert_eq!(flags.read_matching(), Some(false));
+        ///     assert_eq!(flags.add(1).read_matching(), Some(true));
+        /// }
+        /// ```
+        ///
+        /// Bytes that are not a valid `T` are rejected instead of being read.
+        ///
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// let byte = 2u8;
+        /// let flag = new_pointer((&raw const byte).cast::<bool>());
+        ///
+        /// assert_eq!(unsafe { flag.read_matching() }, None);
+        /// ```
+        ///
+        /// [`TryFromBytes::try_read_from_bytes()`]: zerocopy::TryFromBytes::try_read_from_bytes
+        /// [valid]: https://doc.rust-lang.org/core/ptr/index.html#safety
+        #[inline(always)]
+        pub unsafe fn read_matching(self) -> Option<T> {
+            let bytes = core::slice::from_raw_parts(self.0.cast::<u8>(), core::mem::size_of::<T>());
+            T::try_read_from_bytes(bytes).ok()
+        }
+    }
+
+    /// A trait for types whose size can be found from a pointer to them, without reading
+    /// through the pointer.
+    ///
+    /// This is like [`size_of_val()`](core::mem::size_of_val), except that it works on pointers
+    /// that may not be valid to turn into a reference. It is implemented for every sized type,
+    /// slices, and `str`, and can be implemented for custom dynamically sized types.
+    ///
+    /// # Safety
+    /// * [`size_of_raw()`](Self::size_of_raw) must return the same size in bytes that
+    ///   [`size_of_val()`](core::mem::size_of_val) would for a reference to the pointee,
+    ///   based only on the metadata of `ptr`.
+    /// * `ALIGN` must be the alignment of `Self`.
+    pub unsafe trait RawLayout {
+        /// The alignment of `Self` in bytes.
+        const ALIGN: usize;
+        /// Returns the size in bytes of the value that `ptr` points to.
+        fn size_of_raw(ptr: *const Self) -> usize;
+    }
+
+    unsafe impl<T> RawLayout for T {
+        const ALIGN: usize = core::mem::align_of::<T>();
+        #[inline(always)]
+        fn size_of_raw(_: *const Self) -> usize {
+            core::mem::size_of::<T>()
+        }
+    }
+
+    unsafe impl<T> RawLayout for [T] {
+        const ALIGN: usize = core::mem::align_of::<T>();
+        #[inline(always)]
+        fn size_of_raw(ptr: *const Self) -> usize {
+            ptr.len() * core::mem::size_of::<T>()
+        }
+    }
+
+    unsafe impl RawLayout for str {
+        const ALIGN: usize = 1;
+        #[inline(always)]
+        fn size_of_raw(ptr: *const Self) -> usize {
+            (ptr as *const [u8]).len()
+        }
+    }
+
+    /// A trait for types that can check whether a bit pattern is a valid value of the type.
+    ///
+    /// This is used by [`Pointer::read_valid()`] to avoid creating invalid values,
+    /// like a `bool` that is not `0` or `1`.
+    ///
+    /// # Safety
+    /// * [`is_valid()`](Self::is_valid) must only return true if `bits` holds a valid `Self`.
+    /// * [`is_valid()`](Self::is_valid) may only read the bytes of `bits` that are always
+    ///   initialized in a valid `Self`, so it must not read any padding. The caller makes
+    ///   sure that those bytes are initialized.
+    pub unsafe trait ValidBitPattern: Sized {
+        /// Returns whether `bits` holds a valid `Self`.
+        ///
+        /// # Safety
+        /// Every byte of `bits` that is initialized in a valid `Self` must be initialized.
+        /// Only the padding may be uninitialized.
+        unsafe fn is_valid(bits: &MaybeUninit<Self>) -> bool;
+    }
+
+    unsafe impl ValidBitPattern for bool {
+        #[inline(always)]
+        unsafe fn is_valid(bits: &MaybeUninit<Self>) -> bool {
+            // Safety
+            // a `bool` is a single byte, which the caller has initialized.
+            bits.as_ptr().cast::<u8>().read() <= 1
+        }
+    }
+
+    unsafe impl ValidBitPattern for char {
+        #[inline(always)]
+        unsafe fn is_valid(bits: &MaybeUninit<Self>) -> bool {
+            // Safety
+            // a `char` is a `u32` without padding, which the caller has initialized.
+            char::from_u32(bits.as_ptr().cast::<u32>().read()).is_some()
+        }
+    }
+
+    macro_rules! impl_valid_bit_pattern_nonzero {
+        ($($int:ty),* $(,)?) => {$(
+            unsafe impl ValidBitPattern for core::num::NonZero<$int> {
+                #[inline(always)]
+                unsafe fn is_valid(bits: &MaybeUninit<Self>) -> bool {
+                    // Safety
+                    // a `NonZero<T>` has the same layout as `T`, which the caller has initialized.
+                    bits.as_ptr().cast::<$int>().read() != 0
+                }
+            }
+        )*};
+    }
+
+    impl_valid_bit_pattern_nonzero!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+    /// The error returned by [`Pointer::read_valid()`] when the bits are not a valid value.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct InvalidBits;
+
+    impl core::fmt::Display for InvalidBits {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.write_str("invalid bit pattern for the type being read")
+        }
+    }
+
+    impl core::error::Error for InvalidBits {}
+
+    impl<M: Mutability, T: ValidBitPattern> Pointer<M, T> {
+        /// Reads the value from behind this pointer, returning an error if the bits
+        /// are not a valid `T`.
+        ///
+        /// The bits are first read as a [`MaybeUninit<T>`](MaybeUninit), and then checked with
+        /// [`ValidBitPattern::is_valid()`]. Unlike [`read()`](Self::read), the pointee does not
+        /// need to be a valid `T`, so this can be used on untrusted memory.
+        ///
+        /// # Safety
+        /// * The pointer must be [valid] for reads and aligned.
+        /// * Every byte that is initialized in a valid `T` must be initialized.
+        ///
+        /// [valid]: https://doc.rust-lang.org/core/ptr/index.html#safety
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::{new_pointer, InvalidBits};
+        /// use std::num::NonZeroU32;
+        ///
+        /// let bytes = [0u8, 1, 2];
+        /// let ptr = new_pointer(bytes.as_ptr().cast::<bool>());
+        /// unsafe {
+        ///     assert_eq!(ptr.read_valid(), Ok(false));
+        ///     assert_eq!(ptr.add(1).read_valid(), Ok(true));
+        ///     assert_eq!(ptr.add(2).read_valid(), Err(InvalidBits));
+        /// }
+        ///
+        /// let ints = [5u32, 0];
+        /// let ptr = new_pointer(ints.as_ptr().cast::<NonZeroU32>());
+        /// unsafe {
+        ///     assert_eq!(ptr.read_valid(), Ok(NonZeroU32::new(5).unwrap()));
+        ///     assert_eq!(ptr.add(1).read_valid(), Err(InvalidBits));
+        /// }
+        /// ```
+        #[inline(always)]
+        pub unsafe fn read_valid(self) -> Result<T, InvalidBits> {
+            let bits = self.0.cast::<MaybeUninit<T>>().read();
+            // Safety
+            // the caller promised that the bytes of a valid `T` are initialized.
+            if T::is_valid(&bits) {
+                Ok(bits.assume_init())
+            } else {
+                Err(InvalidBits)
+            }
+        }
+    }
+
+    impl<M: Mutability, T: CanIndex + ?Sized> Pointer<M, T> {
+        /// Creates a slice pointer to the elements `[start, end)` of the sequence behind this pointer,
+        /// keeping mutability intact.
+        ///
+        /// This does not read any memory, and is what the `[start..end]` access uses.
+        ///
+        /// # Safety
+        /// All of the [requirements][offsetreq] for [`pointer::add()`] must be upheld
+        /// when moving the pointer forward by `start` elements.
+        ///
+        /// # Panics
+        /// Panics if `start > end`.
+        ///
+        /// [offsetreq]: https://doc.rust-lang.org/core/primitive.pointer.html#safety-2
+        /// [`pointer::add()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.add
+        #[inline(always)]
+        pub const unsafe fn slice_range(self, start: usize, end: usize) -> Pointer<M, [T::E]> {
+            assert!(start <= end, "slice range starts after it ends");
+            let base = self.0.cast::<T::E>().add(start);
+            Pointer(
+                core::ptr::slice_from_raw_parts(base, end - start),
+                PhantomData,
+            )
+        }
+        /// Creates a slice pointer to the elements `[start, end]` of the sequence behind this pointer,
+        /// keeping mutability intact.
+        ///
+        /// This does not read any memory, and is what the `[start..=end]` access uses.
+        ///
+        /// # Safety
+        /// All of the [requirements][offsetreq] for [`pointer::add()`] must be upheld
+        /// when moving the pointer forward by `start` elements.
+        ///
+        /// # Panics
+        /// Panics if `start > end`, or if `end` is [`usize::MAX`].
+        ///
+        /// [offsetreq]: https://doc.rust-lang.org/core/primitive.pointer.html#safety-2
+        /// [`pointer::add()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.add
+        #[inline(always)]
+        pub const unsafe fn slice_range_inclusive(
+            self,
+            start: usize,
+            end: usize,
+        ) -> Pointer<M, [T::E]> {
+            let Some(end) = end.checked_add(1) else {
+                panic!("inclusive slice range ends at `usize::MAX`");
+            };
+            self.slice_range(start, end)
+        }
+        /// Copies `dst.len()` elements from the sequence behind this pointer into `dst`.
+        ///
+        /// This function is a wrapper around [`pointer::copy_to_nonoverlapping()`].
+        /// The elements are copied bitwise, so like [`read()`](Pointer::read) this can
+        /// duplicate values that are not [`Copy`]. The old elements of `dst` are overwritten
+        /// without being dropped, so they are leaked if they implement [`Drop`].
+        ///
+        /// # Safety
+        /// * The sequence must contain at least `dst.len()` elements.
+        /// * Each of those elements must be initialized.
+        /// * The sequence must not overlap with `dst`.
+        /// * See [`pointer::copy_to_nonoverlapping()`] for the other safety requirements.
+        ///
+        /// [`pointer::copy_to_nonoverlapping()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.copy_to_nonoverlapping
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        ///
+        /// struct Frame {
+        ///     id: u8,
+        ///     payload: [u8; 8],
+        /// }
+        ///
+        /// let mut frame = Frame { id: 0, payload: [0; 8] };
+        /// let payload = new_pointer(unsafe { element_ptr!(&raw mut frame => .payload) });
+        ///
+        /// unsafe { payload.init_from_slice(&[1, 2, 3]) };
+        /// assert_eq!(frame.payload, [1, 2, 3, 0, 0, 0, 0, 0]);
+        ///
+        /// let mut dst = [0; 4];
+        /// unsafe { payload.read_into_slice(&mut dst) };
+        /// assert_eq!(dst, [1, 2, 3, 0]);
+        /// ```
+        ///
+        /// The elements that were in `dst` are leaked.
+        ///
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        /// use std::{mem::ManuallyDrop, rc::Rc};
+        ///
+        /// let old = Rc::new(0);
+        /// let mut dst = [Rc::clone(&old)];
+        ///
+        /// let src = ManuallyDrop::new([Rc::new(1)]);
+        /// unsafe { new_pointer(&raw const *src).read_into_slice(&mut dst) };
+        ///
+        /// assert_eq!(*dst[0], 1);
+        /// // the clone that was in `dst` was never dropped.
+        /// assert_eq!(Rc::strong_count(&old), 2);
+        /// ```
+        #[inline(always)]
+        pub const unsafe fn read_into_slice(self, dst: &mut [T::E]) {
+            let len = dst.len();
+            self.0
+                .cast::<T::E>()
+                .copy_to_nonoverlapping(dst.as_mut_ptr(), len);
+        }
+        /// Copies `dst.len()` elements from the sequence behind this pointer into `dst`.
+        ///
+        /// This is the same as [`read_into_slice()`](Self::read_into_slice), but only for
+        /// [`Copy`] elements, like [`slice::copy_from_slice()`].
+        ///
+        /// # Safety
+        /// * The sequence must contain at least `dst.len()` elements.
+        /// * Each of those elements must be initialized.
+        /// * The sequence must not overlap with `dst`.
+        /// * See [`pointer::copy_to_nonoverlapping()`] for the other safety requirements.
+        ///
+        /// [`slice::copy_from_slice()`]: https://doc.rust-lang.org/core/primitive.slice.html#method.copy_from_slice
+        /// [`pointer::copy_to_nonoverlapping()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.copy_to_nonoverlapping
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// let mut samples = [0i16; 6];
+        /// let ptr = new_pointer(&raw mut samples);
+        ///
+        /// unsafe { ptr.slice_range(2, 6).copy_from_slice(&[-1, 2, -3]) };
+        /// assert_eq!(samples, [0, 0, -1, 2, -3, 0]);
+        ///
+        /// let mut window = [0; 3];
+        /// unsafe { ptr.slice_range(1, 6).copy_to_slice(&mut window) };
+        /// assert_eq!(window, [0, -1, 2]);
+        /// ```
+        #[inline(always)]
+        pub const unsafe fn copy_to_slice(self, dst: &mut [T::E])
+        where
+            T::E: Copy,
+        {
+            self.read_into_slice(dst)
+        }
+        /// Copies the first `N` elements of the sequence behind this pointer into an array,
+        /// without requiring them to be initialized.
+        ///
+        /// Unlike reading the elements as `T::E`, reading them as [`MaybeUninit`]
+        /// is allowed even if some of them are uninitialized, so this can be used to
+        /// inspect partially initialized sequences.
+        ///
+        /// # Safety
+        /// * The sequence must contain at least `N` elements.
+        /// * The pointer must be [valid] for reads and aligned.
+        ///
+        /// [valid]: https://doc.rust-lang.org/core/ptr/index.html#safety
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        /// use std::mem::MaybeUninit;
+        ///
+        /// let mut slots = MaybeUninit::<[u32; 4]>::uninit();
+        /// let ptr = new_pointer(slots.as_mut_ptr());
+        ///
+        /// // only the first two slots have been filled in so far.
+        /// unsafe {
+        ///     ptr.cast::<u32>().write(7);
+        ///     ptr.cast::<u32>().add(1).write(8);
+        /// }
+        ///
+        /// let copy = unsafe { ptr.read_array_uninit::<3>() };
+        ///
+        /// assert_eq!(unsafe { (copy[0].assume_init(), copy[1].assume_init()) }, (7, 8));
+        /// ```
+        #[inline(always)]
+        pub const unsafe fn read_array_uninit<const N: usize>(self) -> [MaybeUninit<T::E>; N] {
+            self.0.cast::<[MaybeUninit<T::E>; N]>().read()
+        }
+        /// Reads the first `N` elements of the sequence behind this pointer into an array.
+        ///
+        /// Each element is read with [`pointer::read_volatile()`], one at a time in
+        /// increasing index order. Every read is treated as a side effect,
+        /// so none of them will be combined or skipped.
+        /// This is useful for reading frames out of a DMA buffer.
+        ///
+        /// # Safety
+        /// * The sequence must contain at least `N` elements.
+        /// * All of the requirements of [`pointer::read_volatile()`] must be upheld for each element.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        ///
+        /// struct Dma {
+        ///     head: usize,
+        ///     ring: [u16; 8],
+        /// }
+        ///
+        /// let dma = Dma { head: 2, ring: [0, 0, 10, 11, 12, 13, 0, 0] };
+        /// let base = &raw const dma;
+        ///
+        /// let frame = unsafe {
+        ///     let head = element_ptr!(base => .head.*);
+        ///     new_pointer(element_ptr!(base => .ring)).slice_range(head, 8).read_volatile_frame::<4>()
+        /// };
+        /// assert_eq!(frame, [10, 11, 12, 13]);
+        /// ```
+        ///
+        /// [`pointer::read_volatile()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read_volatile
+        #[inline(always)]
+        pub unsafe fn read_volatile_frame<const N: usize>(self) -> [T::E; N] {
+            let base = self.0.cast::<T::E>();
+            core::array::from_fn(|i| base.add(i).read_volatile())
+        }
+    }
+
+    impl<M: CanWrite, T: CanIndex + ?Sized> Pointer<M, T> {
+        /// Initializes the first `src.len()` elements of the sequence behind
+        /// this pointer by copying them from `src`.
+        ///
+        /// This function is a wrapper around [`pointer::copy_from_nonoverlapping()`].
+        /// The previous elements are overwritten without being dropped.
+        ///
+        /// # Safety
+        /// * The sequence must have room for at least `src.len()` elements.
+        /// * The sequence must not overlap with `src`.
+        /// * See [`pointer::copy_from_nonoverlapping()`] for the other safety requirements.
+        ///
+        /// [`pointer::copy_from_nonoverlapping()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.copy_from_nonoverlapping
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        /// use std::mem::MaybeUninit;
+        ///
+        /// let mut table = MaybeUninit::<[u16; 4]>::uninit();
+        /// let ptr = new_pointer(table.as_mut_ptr());
+        ///
+        /// unsafe {
+        ///     ptr.init_from_slice(&[1, 2]);
+        ///     ptr.slice_range(2, 4).init_from_slice(&[3, 4]);
+        /// }
+        /// assert_eq!(unsafe { table.assume_init() }, [1, 2, 3, 4]);
+        /// ```
+        #[inline(always)]
+        pub const unsafe fn init_from_slice(self, src: &[T::E])
+        where
+            T::E: Copy,
+        {
+            self.0
+                .cast::<T::E>()
+                .cast_mut()
+                .copy_from_nonoverlapping(src.as_ptr(), src.len());
+        }
+        /// Copies all of the elements of `src` into the start of the sequence behind this pointer.
+        ///
+        /// This is the same as [`init_from_slice()`](Self::init_from_slice), and is named
+        /// after [`slice::copy_from_slice()`]. See [`copy_to_slice()`](Self::copy_to_slice)
+        /// for an example.
+        ///
+        /// # Safety
+        /// * The sequence must have room for at least `src.len()` elements.
+        /// * The sequence must not overlap with `src`.
+        /// * See [`pointer::copy_from_nonoverlapping()`] for the other safety requirements.
+        ///
+        /// [`slice::copy_from_slice()`]: https://doc.rust-lang.org/core/primitive.slice.html#method.copy_from_slice
+        /// [`pointer::copy_from_nonoverlapping()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.copy_from_nonoverlapping
+        #[inline(always)]
+        pub const unsafe fn copy_from_slice(self, src: &[T::E])
+        where
+            T::E: Copy,
+        {
+            self.init_from_slice(src)
+        }
+    }
+
+    impl<M: CanWrite, T: ?Sized> Pointer<M, T> {
+        /// Converts this pointer into a shared pointer to a [`Cell<T>`].
+        ///
+        /// This is the pointer version of [`Cell::from_mut()`], and is useful for
+        /// handing out shared, interior mutable access to an element.
+        /// [`Cell<T>`] has the same layout as `T`, so this does not read any memory.
+        ///
+        /// The usual aliasing rules still apply: while a reference to the [`Cell<T>`]
+        /// exists, the pointee must not be accessed through anything other than that cell.
+        ///
+        /// [`Cell<T>`]: core::cell::Cell
+        /// [`Cell::from_mut()`]: core::cell::Cell::from_mut
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        ///
+        /// struct Stats {
+        ///     hits: u32,
+        ///     misses: u32,
+        /// }
+        ///
+        /// let mut stats = Stats { hits: 0, misses: 0 };
+        /// let base = &raw mut stats;
+        ///
+        /// let hits = unsafe { &*new_pointer(element_ptr!(base => .hits)).as_cell().into_inner() };
+        /// hits.set(hits.get() + 1);
+        /// hits.set(hits.get() + 1);
+        ///
+        /// assert_eq!(stats.hits, 2);
+        /// ```
+        #[inline(always)]
+        pub const fn as_cell(self) -> Pointer<Const, Cell<T>> {
+            Pointer(self.0 as *const Cell<T>, PhantomData)
+        }
+    }
+
+    impl<M: CanWrite, T> Pointer<M, T> {
+        /// Overwrites the value behind this pointer without reading or dropping the old value.
+        ///
+        /// This function is a wrapper around [`pointer::write()`].
+        ///
+        /// # Safety
+        /// * See [`pointer::write()`] for the safety requirements.
+        ///
+        /// [`pointer::write()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.write
+        #[inline(always)]
+        pub const unsafe fn write(self, value: T) {
+            (self.0 as *mut T).write(value)
+        }
+        /// Writes `value` behind this pointer and returns a mutable reference to it.
+        ///
+        /// This is the same as [`write()`](Self::write) followed by turning the pointer into a
+        /// reference, like [`MaybeUninit::write()`], and is useful for initializing an element
+        /// in place and then using it right away. The old value is not dropped.
+        ///
+        /// # Safety
+        /// * The same requirements as [`write()`](Self::write) apply.
+        /// * The lifetime `'a` is unbounded, so the reference must not outlive the pointee.
+        /// * While the reference is alive, the pointee must not be accessed through any other
+        ///   pointer or reference, including ones to the containing value.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::element_ptr;
+        /// use std::mem::MaybeUninit;
+        ///
+        /// struct Connection {
+        ///     id: u32,
+        ///     buffer: Vec<u8>,
+        /// }
+        ///
+        /// let mut slot = MaybeUninit::<Connection>::uninit();
+        ///
+        /// let connection = unsafe {
+        ///     element_ptr!(uninit &mut slot => .id <- 9);
+        ///     let buffer = element_ptr!(uninit &mut slot => .buffer ~).init(Vec::new());
+        ///     buffer.extend_from_slice(b"ping");
+        ///     slot.assume_init()
+        /// };
+        ///
+        /// assert_eq!((connection.id, &connection.buffer[..]), (9, &b"ping"[..]));
+        /// ```
+        #[inline(always)]
+        pub unsafe fn init<'a>(self, value: T) -> &'a mut T {
+            let ptr = self.0 as *mut T;
+            ptr.write(value);
+            &mut *ptr
+        }
+        /// Overwrites the value behind this pointer with a volatile write, without reading
+        /// or dropping the old value.
+        ///
+        /// This function is a wrapper around [`pointer::write_volatile()`].
+        ///
+        /// # Safety
+        /// * See [`pointer::write_volatile()`] for the safety requirements.
+        ///
+        /// [`pointer::write_volatile()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.write_volatile
+        #[inline(always)]
+        pub unsafe fn write_volatile(self, value: T) {
+            (self.0 as *mut T).write_volatile(value)
+        }
+        /// Overwrites the value behind this pointer without requiring it to be aligned,
+        /// and without reading or dropping the old value.
+        ///
+        /// This function is a wrapper around [`pointer::write_unaligned()`].
+        ///
+        /// # Safety
+        /// * See [`pointer::write_unaligned()`] for the safety requirements.
+        ///
+        /// [`pointer::write_unaligned()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.write_unaligned
+        #[inline(always)]
+        pub const unsafe fn write_unaligned(self, value: T) {
+            (self.0 as *mut T).write_unaligned(value)
+        }
+        /// Swaps `count` elements starting at this pointer with `count` elements starting at `other`.
+        ///
+        /// This function is a wrapper around [`ptr::swap_nonoverlapping()`], and is useful for
+        /// moving runs of elements around in raw memory, like in partitioning or merging.
+        ///
+        /// # Safety
+        /// * Both pointers must be valid for reads and writes of `count` elements, and aligned.
+        /// * The two runs of elements must not overlap.
+        /// * See [`ptr::swap_nonoverlapping()`] for the other safety requirements.
+        ///
+        /// [`ptr::swap_nonoverlapping()`]: core::ptr::swap_nonoverlapping
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        ///
+        /// let mut left = [1, 2, 3, 4];
+        /// let mut right = [5, 6, 7, 8];
+        ///
+        /// unsafe {
+        ///     let left = new_pointer(element_ptr!(&raw mut left => [1]));
+        ///     let right = new_pointer(element_ptr!(&raw mut right => [0]));
+        ///     left.swap_with(right, 3);
+        /// }
+        ///
+        /// assert_eq!(left, [1, 5, 6, 7]);
+        /// assert_eq!(right, [2, 3, 4, 8]);
+        /// ```
+        #[inline(always)]
+        pub unsafe fn swap_with<N: CanWrite>(self, other: Pointer<N, T>, count: usize) {
+            core::ptr::swap_nonoverlapping(self.0 as *mut T, other.0 as *mut T, count)
+        }
+        /// Moves the value out from behind this pointer, leaving `default` in its place.
+        ///
+        /// This function is a wrapper around [`pointer::replace()`], and is the pointer
+        /// version of [`mem::replace()`](core::mem::replace). Unlike [`read()`](Self::read),
+        /// the slot keeps owning a value afterwards, so nothing is owned twice if a panic
+        /// happens before the taken value is put somewhere else.
+        ///
+        /// # Safety
+        /// * The pointee must be initialized.
+        /// * See [`pointer::replace()`] for the other safety requirements.
+        ///
+        /// [`pointer::replace()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.replace
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        ///
+        /// struct User {
+        ///     id: u32,
+        ///     name: String,
+        /// }
+        ///
+        /// let mut user = User { id: 1, name: String::from("ferris") };
+        /// let ptr = &raw mut user;
+        ///
+        /// let name = unsafe { new_pointer(element_ptr!(ptr => .name)).take(String::new()) };
+        ///
+        /// assert_eq!(name, "ferris");
+        /// assert_eq!(user.name, "");
+        /// ```
+        #[inline(always)]
+        pub unsafe fn take(self, default: T) -> T {
+            (self.0 as *mut T).replace(default)
+        }
+        /// Replaces the value behind this pointer with the result of calling `f` on it,
+        /// using volatile accesses.
+        ///
+        /// The old value is read with [`pointer::read_volatile()`], passed to `f`, and then
+        /// the result is written with [`pointer::write_volatile()`]. These are two separate
+        /// accesses, and the write always happens after the read. Nothing stops something else
+        /// from writing between them, so this is not atomic.
+        ///
+        /// # Safety
+        /// All of the requirements of both [`pointer::read_volatile()`] and
+        /// [`pointer::write_volatile()`] must be upheld.
+        ///
+        /// [`pointer::read_volatile()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read_volatile
+        /// [`pointer::write_volatile()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.write_volatile
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// // a counter register, where the top bit enables it.
+        /// let mut register = 0x0000_0041u32;
+        /// let ptr = new_pointer(&raw mut register);
+        ///
+        /// unsafe {
+        ///     ptr.update_volatile(|value| (value + 1) | 1 << 31);
+        /// }
+        /// assert_eq!(register, 0x8000_0042);
+        /// ```
+        #[inline(always)]
+        pub unsafe fn update_volatile(self, f: impl FnOnce(T) -> T) {
+            let ptr = self.0 as *mut T;
+            ptr.write_volatile(f(ptr.read_volatile()));
+        }
+    }
+
+    impl<M: CanWrite, T> Pointer<M, T>
+    where
+        T: Copy + BitAnd<Output = T> + BitOr<Output = T> + Not<Output = T>,
+    {
+        /// Changes the bits selected by `mask` to the bits of `value`, leaving the rest as they were.
+        ///
+        /// This does a read-modify-write, like is usually needed for memory mapped registers.
+        /// The old value is read with [`pointer::read_volatile()`], then the new value
+        /// `(old & !mask) | (value & mask)` is written with [`pointer::write_volatile()`].
+        ///
+        /// These are two separate accesses, and the write always happens after the read.
+        /// Nothing stops something else from writing between them, so this is not atomic.
+        ///
+        /// # Safety
+        /// All of the requirements of both [`pointer::read_volatile()`] and
+        /// [`pointer::write_volatile()`] must be upheld.
+        ///
+        /// [`pointer::read_volatile()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read_volatile
+        /// [`pointer::write_volatile()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.write_volatile
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// let mut register = 0b1010_1010u8;
+        /// let ptr = new_pointer(&raw mut register);
+        ///
+        /// unsafe {
+        ///     ptr.modify_bits(0b0000_1111, 0b0000_0101);
+        ///     assert_eq!(register, 0b1010_0101);
+        ///
+        ///     // bits of `value` outside of `mask` are ignored.
+        ///     ptr.modify_bits(0b1100_0000, 0b0111_1111);
+        ///     assert_eq!(register, 0b0110_0101);
+        /// }
+        /// ```
+        #[inline(always)]
+        pub unsafe fn modify_bits(self, mask: T, value: T) {
+            let old = self.0.read_volatile();
+            (self.0 as *mut T).write_volatile((old & !mask) | (value & mask));
+        }
+    }
+
+    impl<M: Mutability, T: ?Sized> Pointer<M, UnsafeCell<T>> {
+        /// Returns a pointer to the value inside of the [`UnsafeCell`].
+        ///
+        /// This uses [`UnsafeCell::raw_get()`], so no reference to the cell is ever created.
+        /// The mutability of the pointer is kept, so writing to the value still needs a
+        /// pointer that can be written through.
+        #[inline(always)]
+        pub const fn cell_inner(self) -> Pointer<M, T> {
+            Pointer(UnsafeCell::raw_get(self.0), PhantomData)
+        }
+    }
+
+    impl<M: Mutability, T: ?Sized> Pointer<M, Cell<T>> {
+        /// Returns a pointer to the value inside of the [`Cell`].
+        ///
+        /// [`Cell<T>`] has the same layout as [`UnsafeCell<T>`], so this is the same as
+        /// going through [`UnsafeCell::raw_get()`].
+        #[inline(always)]
+        pub const fn cell_inner(self) -> Pointer<M, T> {
+            Pointer(
+                UnsafeCell::raw_get(self.0 as *const UnsafeCell<T>),
+                PhantomData,
+            )
+        }
+    }
+
+    impl<M: Mutability, T> Pointer<M, MaybeUninit<T>> {
+        /// Returns a pointer to the value inside of the [`MaybeUninit`].
+        ///
+        /// This is the pointer version of [`MaybeUninit::as_ptr()`] and [`MaybeUninit::as_mut_ptr()`],
+        /// and is just a cast, because [`MaybeUninit<T>`] has the same layout as `T`.
+        /// The value does not have to be initialized to get the pointer, but it still
+        /// has to be before it is read.
+        #[inline(always)]
+        pub const fn uninit_inner(self) -> Pointer<M, T> {
+            self.cast()
+        }
+    }
+
+    impl<M: Mutability, T> Pointer<M, ManuallyDrop<T>> {
+        /// Returns a pointer to the value inside of the [`ManuallyDrop`].
+        ///
+        /// This is just a cast, because [`ManuallyDrop<T>`] has the same layout as `T`.
+        /// Combined with indexing, this can be used to get to the elements of
+        /// a `[ManuallyDrop<T>; N]`. This is what the `.{md}` access uses.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        /// use std::mem::ManuallyDrop;
+        ///
+        /// let mut names = [
+        ///     ManuallyDrop::new(String::from("a")),
+        ///     ManuallyDrop::new(String::from("b")),
+        /// ];
+        /// let slot = new_pointer(unsafe { element_ptr!(&raw mut names => [1]) });
+        ///
+        /// unsafe {
+        ///     let name = slot.manually_drop_inner();
+        ///     name.into_inner().drop_in_place();
+        ///     name.write(String::from("c"));
+        /// }
+        /// assert_eq!(*names[1], "c");
+        ///
+        /// for name in &mut names {
+        ///     unsafe { ManuallyDrop::drop(name) };
+        /// }
+        /// ```
+        #[inline(always)]
+        pub const fn manually_drop_inner(self) -> Pointer<M, T> {
+            self.cast()
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<M: Mutability, T> Pointer<M, Option<alloc::boxed::Box<T>>> {
+        /// Reads the box from behind this pointer, returning a pointer to its contents
+        /// if it is `Some`, or `None` otherwise.
+        ///
+        /// This relies on the layout of `Option<Box<T>>` being the same as a nullable
+        /// `*const T`, where `None` is the null pointer, which is guaranteed for sized `T`.
+        /// The box itself is not moved or dropped, and the contents are not read.
+        ///
+        /// # Safety
+        /// All of the requirements of [`pointer::read()`] must be upheld for the box.
+        ///
+        /// [`pointer::read()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.read
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        ///
+        /// struct Node {
+        ///     value: u32,
+        ///     next: Option<Box<Node>>,
+        /// }
+        ///
+        /// let list = Node { value: 1, next: Some(Box::new(Node { value: 2, next: None })) };
+        /// let ptr = &list as *const Node;
+        ///
+        /// unsafe {
+        ///     let next = new_pointer(element_ptr!(ptr => .next)).box_contents().unwrap();
+        ///     let next = next.into_inner();
+        ///     assert_eq!(element_ptr!(next => .value).read(), 2);
+        ///
+        ///     let last = new_pointer(element_ptr!(next => .next)).box_contents();
+        ///     assert!(last.is_none());
+        /// }
+        /// ```
+        #[inline(always)]
+        pub unsafe fn box_contents(self) -> Option<Pointer<M, T>> {
+            let contents = self.0.cast::<*const T>().read();
+            if contents.is_null() {
+                None
+            } else {
+                Some(Pointer(contents, PhantomData))
+            }
+        }
+    }
+
+    impl<M: Mutability, T, const N: usize> Pointer<M, [T; N]> {
+        /// Splits this array pointer into a pointer to its first element and its length.
+        ///
+        /// This is useful for APIs that take a pointer and a length, such as many FFI functions.
+        /// This does not read any memory.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        ///
+        /// // a stand-in for a C function that takes a pointer and a length.
+        /// unsafe fn checksum(data: *const u8, len: usize) -> u8 {
+        ///     (0..len).fold(0, |sum, i| sum.wrapping_add(unsafe { data.add(i).read() }))
+        /// }
+        ///
+        /// struct Packet {
+        ///     kind: u8,
+        ///     body: [u8; 4],
+        /// }
+        ///
+        /// let packet = Packet { kind: 1, body: [1, 2, 3, 4] };
+        /// let body = new_pointer(unsafe { element_ptr!(&packet => .body) });
+        ///
+        /// let (data, len) = body.as_slice_parts();
+        /// assert_eq!(len, 4);
+        /// assert_eq!(unsafe { checksum(data.into_inner(), len) }, 10);
+        /// ```
+        #[inline(always)]
+        pub const fn as_slice_parts(self) -> (Pointer<M, T>, usize) {
+            (self.cast(), N)
+        }
+        /// Returns the length of the array, `N`.
+        #[inline(always)]
+        pub const fn len(self) -> usize {
+            N
+        }
+        /// Returns whether the array is empty, which is when `N` is `0`.
+        #[inline(always)]
+        pub const fn is_empty(self) -> bool {
+            N == 0
+        }
+        /// Creates a slice pointer to the first `len` elements of this array, returning `None`
+        /// if `len` is greater than the length of the array `N`.
+        ///
+        /// This is useful when the length comes from untrusted data, such as a count stored in
+        /// a buffer, since the resulting slice is always within the bounds of the array.
+        /// This does not read any memory.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// let buf = [1u32, 2, 3, 4];
+        /// let ptr = new_pointer(&raw const buf);
+        ///
+        /// let slice = ptr.as_slice_checked(3).unwrap().into_inner();
+        /// assert_eq!(unsafe { &*slice }, &[1, 2, 3]);
+        /// assert_eq!(ptr.as_slice_checked(4).unwrap().into_inner().len(), 4);
+        /// assert_eq!(ptr.as_slice_checked(0).unwrap().into_inner().len(), 0);
+        ///
+        /// assert!(ptr.as_slice_checked(5).is_none());
+        /// assert!(ptr.as_slice_checked(usize::MAX).is_none());
+        /// ```
+        #[inline(always)]
+        pub const fn as_slice_checked(self, len: usize) -> Option<Pointer<M, [T]>> {
+            if len > N {
+                None
+            } else {
+                Some(self.as_slice_len(len))
+            }
+        }
+    }
+
+    impl<M: Mutability, T> Pointer<M, [T]> {
+        /// Returns the length of the slice.
+        ///
+        /// This function is a wrapper around [`pointer::len()`], so it does not read any memory.
+        ///
+        /// [`pointer::len()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.len
+        #[inline(always)]
+        pub const fn len(self) -> usize {
+            self.0.len()
+        }
+        /// Returns whether the slice has a length of `0`.
+        #[inline(always)]
+        pub const fn is_empty(self) -> bool {
+            self.0.len() == 0
+        }
+        /// Reinterprets this slice as a slice of `U`s, followed by the `T`s that
+        /// were left over.
+        ///
+        /// This is similar to [`slice::align_to()`], except that there is no prefix
+        /// and the alignment of the pointer is not checked. The middle slice is the
+        /// longest that still ends on a boundary between two `T`s, so the bytes
+        /// of the two slices always add up to the bytes of `self`.
+        /// If either `T` or `U` is zero sized, the middle slice is empty.
+        ///
+        /// This does not read any memory. To use the middle slice, the pointer
+        /// must be aligned for `U`.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// let halves = [1u16; 7];
+        /// let (words, rest) = new_pointer(&halves as *const [u16]).as_slice_with_remainder::<u32>();
+        /// assert_eq!((words.len(), rest.len()), (3, 1));
+        /// assert_eq!(rest.into_inner().cast::<u16>(), &halves[6] as *const u16);
+        ///
+        /// // when the bytes divide evenly, the remainder is empty.
+        /// let (words, rest) = new_pointer(&halves[..6] as *const [u16]).as_slice_with_remainder::<u32>();
+        /// assert_eq!((words.len(), rest.len()), (3, 0));
+        ///
+        /// // the middle slice only ends where both a `T` and a `U` end.
+        /// let triples = [[0u8; 3]; 5];
+        /// let (halves, rest) = new_pointer(&triples as *const [[u8; 3]]).as_slice_with_remainder::<u16>();
+        /// assert_eq!((halves.len(), rest.len()), (6, 1));
+        ///
+        /// // a zero sized `U` leaves everything in the remainder.
+        /// let (units, rest) = new_pointer(&triples as *const [[u8; 3]]).as_slice_with_remainder::<()>();
+        /// assert_eq!((units.len(), rest.len()), (0, 5));
+        ///
+        /// // the slice doesn't have to point to anything.
+        /// let dangling = core::ptr::slice_from_raw_parts(core::ptr::null::<u16>(), 3);
+        /// let (words, rest) = new_pointer(dangling).as_slice_with_remainder::<u32>();
+        /// assert_eq!((words.len(), rest.len()), (1, 1));
+        /// ```
+        ///
+        /// [`slice::align_to()`]: https://doc.rust-lang.org/core/primitive.slice.html#method.align_to
+        #[inline(always)]
+        pub const fn as_slice_with_remainder<U>(self) -> (Pointer<M, [U]>, Pointer<M, [T]>) {
+            let t_size = core::mem::size_of::<T>();
+            let u_size = core::mem::size_of::<U>();
+            let len = self.0.len();
+            let (t_count, u_count) = if t_size == 0 || u_size == 0 {
+                (0, 0)
+            } else {
+                // the smallest span of bytes that is a whole number of both `T`s and `U`s.
+                let lcm = t_size / gcd(t_size, u_size) * u_size;
+                // the length of a raw slice isn't limited, so this avoids `len * t_size`.
+                let chunks = len / (lcm / t_size);
+                (chunks * (lcm / t_size), chunks.wrapping_mul(lcm / u_size))
+            };
+            let base = self.0.cast::<T>();
+            // the slice may not point to anything, so this can't use `add`.
+            let rest = base.wrapping_add(t_count);
+            (
+                Pointer(
+                    core::ptr::slice_from_raw_parts(base.cast::<U>(), u_count),
+                    PhantomData,
+                ),
+                Pointer(
+                    core::ptr::slice_from_raw_parts(rest, len - t_count),
+                    PhantomData,
+                ),
+            )
+        }
+        /// Splits this slice into two at the index `mid`.
+        ///
+        /// The first slice contains the elements `[0, mid)`, and the second contains
+        /// the elements `[mid, len)`. Either of them may be empty.
+        ///
+        /// # Safety
+        /// All of the [requirements][offsetreq] for [`pointer::add()`] must be upheld
+        /// when moving the pointer forward by `mid` elements.
+        ///
+        /// # Panics
+        /// Panics if `mid > len`.
+        ///
+        /// [offsetreq]: https://doc.rust-lang.org/core/primitive.pointer.html#safety-2
+        /// [`pointer::add()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.add
+        #[inline(always)]
+        pub const unsafe fn split_at(self, mid: usize) -> (Self, Self) {
+            let len = self.0.len();
+            assert!(mid <= len, "split index out of bounds");
+            let base = self.0.cast::<T>();
+            (
+                Pointer(core::ptr::slice_from_raw_parts(base, mid), PhantomData),
+                Pointer(
+                    core::ptr::slice_from_raw_parts(base.add(mid), len - mid),
+                    PhantomData,
+                ),
+            )
+        }
+        /// Turns this slice pointer into a shared slice.
+        ///
+        /// This is the bridge from a projected slice pointer back to safe code.
+        /// The lifetime `'a` is unbounded, so the caller must pick one that does
+        /// not outlive the data.
+        ///
+        /// # Safety
+        /// * The pointer must be aligned and non-null, even if the slice is empty.
+        /// * Every element of the slice must be initialized and a valid `T`.
+        /// * The memory must not be mutated for `'a`, except inside of an [`UnsafeCell`].
+        ///
+        /// [`UnsafeCell`]: core::cell::UnsafeCell
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        ///
+        /// struct Buffer {
+        ///     len: usize,
+        ///     data: [u8; 6],
+        /// }
+        ///
+        /// let buffer = Buffer { len: 4, data: *b"abcdef" };
+        /// let ptr = &buffer as *const Buffer;
+        ///
+        /// let used = unsafe {
+        ///     let len = element_ptr!(ptr => .len.*);
+        ///     let data = new_pointer(element_ptr!(ptr => .data));
+        ///     data.as_slice_checked(len).unwrap().as_slice()
+        /// };
+        /// assert_eq!(used, b"abcd");
+        /// ```
+        #[inline(always)]
+        pub const unsafe fn as_slice<'a>(self) -> &'a [T] {
+            &*self.0
+        }
+    }
+
+    impl<M: CanWrite, T> Pointer<M, [T]> {
+        /// Turns this slice pointer into a mutable slice.
+        ///
+        /// The lifetime `'a` is unbounded, so the caller must pick one that does
+        /// not outlive the data.
+        ///
+        /// # Safety
+        /// * The pointer must be aligned and non-null, even if the slice is empty.
+        /// * Every element of the slice must be initialized and a valid `T`.
+        /// * The memory must not be accessed through any other pointer for `'a`.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::{element_ptr, helper::new_pointer};
+        ///
+        /// let mut samples = [0i32; 6];
+        /// let ptr = &mut samples[..] as *mut [i32];
+        ///
+        /// let (left, right) = unsafe { element_ptr!(ptr => [..2 | 2..]) };
+        /// unsafe {
+        ///     new_pointer(left).as_mut_slice().fill(-1);
+        ///     new_pointer(right).as_mut_slice().copy_from_slice(&[1, 2, 3, 4]);
+        /// }
+        ///
+        /// assert_eq!(samples, [-1, -1, 1, 2, 3, 4]);
+        /// ```
+        #[inline(always)]
+        pub const unsafe fn as_mut_slice<'a>(self) -> &'a mut [T] {
+            &mut *(self.0 as *mut [T])
+        }
+    }
+
+    impl<M: Mutability> Pointer<M, [u8]> {
+        /// Splits this byte slice into a prefix, a middle slice of correctly aligned `U`s,
+        /// and a suffix, like [`slice::align_to()`].
+        ///
+        /// The prefix is the smallest number of bytes needed to reach an address aligned
+        /// for `U`, as computed by [`pointer::align_offset()`]. The middle slice then holds
+        /// as many whole `U`s as fit in the rest of the bytes, and the suffix holds whatever
+        /// is left over. The three slices always add up to the bytes of `self`.
+        ///
+        /// The middle slice may be empty, for example if the slice is too short to reach
+        /// an aligned address, or if `U` is zero sized. In that case every byte is in the
+        /// prefix or the suffix.
+        ///
+        /// This does not read any memory.
+        ///
+        /// [`slice::align_to()`]: https://doc.rust-lang.org/core/primitive.slice.html#method.align_to
+        /// [`pointer::align_offset()`]: https://doc.rust-lang.org/core/primitive.pointer.html#method.align_offset
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// #[repr(C, align(8))]
+        /// struct Buffer([u8; 32]);
+        ///
+        /// let buf = Buffer([0; 32]);
+        /// for start in 0..8 {
+        ///     for end in start..=32 {
+        ///         let bytes = new_pointer(core::ptr::slice_from_raw_parts(
+        ///             buf.0.as_ptr().wrapping_add(start),
+        ///             end - start,
+        ///         ));
+        ///         let (prefix, middle, suffix) = bytes.align_to::<u64>();
+        ///
+        ///         let expected = unsafe { buf.0[start..end].align_to::<u64>() };
+        ///         assert_eq!(prefix.into_inner().len(), expected.0.len());
+        ///         assert_eq!(middle.into_inner().len(), expected.1.len());
+        ///         assert_eq!(suffix.into_inner().len(), expected.2.len());
+        ///         if !expected.1.is_empty() {
+        ///             assert_eq!(middle.into_inner().cast::<u64>(), expected.1.as_ptr());
+        ///         }
+        ///     }
+        /// }
+        /// ```
+        #[inline(always)]
+        pub fn align_to<U>(self) -> (Self, Pointer<M, [U]>, Self) {
+            let u_size = core::mem::size_of::<U>();
+            let base = self.0.cast::<u8>();
+            let len = self.0.len();
+            let offset = base.align_offset(core::mem::align_of::<U>());
+            if u_size == 0 || offset > len {
+                return (
+                    self,
+                    Pointer(core::ptr::slice_from_raw_parts(base.cast(), 0), PhantomData),
+                    Pointer(
+                        core::ptr::slice_from_raw_parts(base.wrapping_add(len), 0),
+                        PhantomData,
+                    ),
+                );
+            }
+            let u_count = (len - offset) / u_size;
+            let middle = base.wrapping_add(offset);
+            let suffix = middle.wrapping_add(u_count * u_size);
+            (
+                Pointer(core::ptr::slice_from_raw_parts(base, offset), PhantomData),
+                Pointer(
+                    core::ptr::slice_from_raw_parts(middle.cast::<U>(), u_count),
+                    PhantomData,
+                ),
+                Pointer(
+                    core::ptr::slice_from_raw_parts(suffix, len - offset - u_count * u_size),
+                    PhantomData,
+                ),
+            )
+        }
+    }
+
+    const fn gcd(mut a: usize, mut b: usize) -> usize {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+
+    // This is a freestanding function for the same reason as `index`,
+    // so that writing through a `*const T` uses the `CanWrite` error message.
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const unsafe fn write<M: CanWrite, T>(ptr: Pointer<M, T>, value: T) {
+        ptr.write(value)
+    }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    pub unsafe fn write_volatile<M: CanWrite, T>(ptr: Pointer<M, T>, value: T) {
+        ptr.write_volatile(value)
+    }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const unsafe fn write_unaligned<M: CanWrite, T>(ptr: Pointer<M, T>, value: T) {
+        ptr.write_unaligned(value)
+    }
+
+    // This only checks that `ptr` can be written through, for the same reason as `write`.
+    // The macro calls `as_mut()` on the result itself, since it gives an `Option` for
+    // a `*mut T` but not for a `NonNull<T>`.
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const fn into_writable<M: CanWrite, T: ?Sized>(ptr: Pointer<M, T>) -> M::Raw<T> {
+        ptr.into_inner()
+    }
+
+    // This is a freestanding function to make the error message
+    // when T doesn't implement `CanIndex` slightly better.
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const unsafe fn index<M: Mutability, T>(
+        ptr: Pointer<M, T>,
+        index: usize,
+    ) -> Pointer<M, T::E>
+    where
+        T: CanIndex + ?Sized,
+    {
+        #[cfg(feature = "debug-checks")]
+        debug_check_count::<T::E>(index);
+        let base = ptr.into_const().cast::<T::E>();
+        let ptr = base.add(index);
+        Pointer(ptr, PhantomData)
+    }
+
+    /// Indexes a sequence of `[E; C]` rows as if it were one flat sequence of `E`,
+    /// which is `row * C + col` elements from the start.
+    ///
+    /// The rows are in row-major order, so this is the same as indexing with `row` and then
+    /// `col`, but `col` is not checked against `C`.
+    ///
+    /// # Panics
+    /// Panics if the flat index, `row * C + col`, overflows a `usize`.
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const unsafe fn multi_index<M: Mutability, T, E, const C: usize>(
+        ptr: Pointer<M, T>,
+        row: usize,
+        col: usize,
+    ) -> Pointer<M, E>
+    where
+        T: CanIndex<E = [E; C]> + ?Sized,
+    {
+        let index = match row.checked_mul(C) {
+            Some(start) => start.checked_add(col),
+            None => None,
+        };
+        let Some(index) = index else {
+            panic!("multi-index does not fit in a `usize`");
+        };
+        #[cfg(feature = "debug-checks")]
+        debug_check_count::<E>(index);
+        let base = ptr.into_const().cast::<E>();
+        let ptr = base.add(index);
+        Pointer(ptr, PhantomData)
+    }
+
+    // No allocated object is larger than `isize::MAX` bytes, so an offset that is larger
+    // than that can never stay in bounds. This is usually a negative number that was cast
+    // to a `usize`. The real bounds of the allocated object are not known here, so anything
+    // smaller than that is not checked.
+    #[cfg(feature = "debug-checks")]
+    #[inline(always)]
+    const fn debug_check_count<T>(count: usize) {
+        debug_assert!(
+            match core::mem::size_of::<T>() {
+                0 => true,
+                size => count <= isize::MAX as usize / size,
+            },
+            "offset is too large to stay inside of an allocated object"
+        );
+    }
+
+    /// The address range of an allocated object, used for debugging.
+    ///
+    /// There is no way to find the real bounds of an allocated object from a pointer,
+    /// so the range has to be given when the tag is created. Because of that, these checks
+    /// only catch pointers that are obviously wrong, and say nothing about provenance.
+    ///
+    /// # Examples
+    /// ```
+    /// use element_ptr::{element_ptr, helper::{new_pointer, AllocationTag}};
+    ///
+    /// struct Header {
+    ///     len: u32,
+    ///     flags: u32,
+    /// }
+    ///
+    /// let header = Header { len: 0, flags: 0 };
+    /// let other = Box::new(0u64);
+    ///
+    /// let base = &raw const header;
+    /// let tag = AllocationTag::of_pointee(new_pointer(base));
+    ///
+    /// assert!(tag.contains(new_pointer(unsafe { element_ptr!(base => .flags) })));
+    /// assert!(!tag.contains(new_pointer(&raw const *other)));
+    /// ```
+    #[cfg(feature = "debug-checks")]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct AllocationTag {
+        start: usize,
+        len: usize,
+    }
+
+    #[cfg(feature = "debug-checks")]
+    impl AllocationTag {
+        /// Creates a tag for the `len` bytes starting at `base`.
+        #[inline(always)]
+        pub fn new<M: Mutability, T: ?Sized>(base: Pointer<M, T>, len: usize) -> Self {
+            Self {
+                start: base.0.addr(),
+                len,
+            }
+        }
+        /// Creates a tag for the single `T` that `base` points to.
+        #[inline(always)]
+        pub fn of_pointee<M: Mutability, T>(base: Pointer<M, T>) -> Self {
+            Self::new(base, core::mem::size_of::<T>())
+        }
+        /// Returns whether the address of `ptr` is inside of this allocation.
+        ///
+        /// The address one past the end of the allocation counts as inside of it.
+        #[inline(always)]
+        pub fn contains<M: Mutability, T: ?Sized>(&self, ptr: Pointer<M, T>) -> bool {
+            ptr.0
+                .addr()
+                .checked_sub(self.start)
+                .is_some_and(|offset| offset <= self.len)
+        }
+    }
+
+    #[cfg(feature = "debug-checks")]
+    impl<M: Mutability, T> Pointer<M, T> {
+        /// Returns whether this pointer is null or has the address
+        /// of [`NonNull::dangling()`](core::ptr::NonNull::dangling).
+        ///
+        /// A pointer with a different address may still be dangling.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        /// use std::ptr::{self, NonNull};
+        ///
+        /// let value = 5u64;
+        ///
+        /// assert!(new_pointer(NonNull::<u64>::dangling()).is_dangling());
+        /// assert!(new_pointer(ptr::null::<u64>()).is_dangling());
+        /// assert!(!new_pointer(&raw const value).is_dangling());
+        /// ```
+        #[inline(always)]
+        pub fn is_dangling(self) -> bool {
+            let addr = self.0.addr();
+            addr == 0 || addr == core::mem::align_of::<T>()
+        }
+    }
+
+    #[cfg(feature = "debug-checks")]
+    impl<M: Mutability, T: ?Sized> Pointer<M, T> {
+        /// Returns whether both this pointer and `other` are inside of the allocation `tag`.
+        ///
+        /// See [`AllocationTag`] for the limitations of this check.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::{new_pointer, AllocationTag};
+        ///
+        /// let first = [0u32; 4];
+        /// let second = Box::new([0u32; 4]);
+        ///
+        /// let start = new_pointer(first.as_ptr());
+        /// let tag = AllocationTag::new(start, size_of_val(&first));
+        ///
+        /// unsafe {
+        ///     assert!(start.same_allocation(start.add(3), tag));
+        ///     // one past the end still counts.
+        ///     assert!(start.same_allocation(start.add(4), tag));
+        /// }
+        /// assert!(!start.same_allocation(new_pointer(second.as_ptr()), tag));
+        /// ```
+        #[inline(always)]
+        pub fn same_allocation<N: Mutability, U: ?Sized>(
+            self,
+            other: Pointer<N, U>,
+            tag: AllocationTag,
+        ) -> bool {
+            tag.contains(self) && tag.contains(other)
+        }
+    }
+
+    /// A hex dump of some bytes, created by [`Pointer::debug_dump()`].
+    ///
+    /// This is formatted as rows of up to 16 bytes, each starting with
+    /// the offset of its first byte.
+    #[cfg(feature = "debug-checks")]
+    #[derive(Clone, Copy)]
+    pub struct HexDump<'a>(&'a [u8]);
+
+    #[cfg(feature = "debug-checks")]
+    impl core::fmt::Display for HexDump<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            for (row, bytes) in self.0.chunks(16).enumerate() {
+                if row != 0 {
+                    f.write_str("\n")?;
+                }
+                write!(f, "{:08x}:", row * 16)?;
+                for byte in bytes {
+                    write!(f, " {byte:02x}")?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "debug-checks")]
+    impl<M: Mutability, T: ?Sized> Pointer<M, T> {
+        /// Returns a hex dump of the `len` bytes starting at this pointer.
+        ///
+        /// This is useful for figuring out the layout of a type, such as one that comes from FFI.
+        ///
+        /// # Safety
+        /// * The pointer must be valid for reads of `len` bytes, and each
+        ///   of those bytes must be initialized.
+        /// * The lifetime `'a` is unbounded, and is not tied to the bytes in any way.
+        ///   The [`HexDump`] must not be used after the bytes are gone.
+        /// * The bytes must not be written to while the [`HexDump`] exists.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::helper::new_pointer;
+        ///
+        /// #[repr(C)]
+        /// struct Header {
+        ///     magic: [u8; 4],
+        ///     version: u16,
+        ///     flags: u16,
+        /// }
+        ///
+        /// let header = Header { magic: *b"ELF\0", version: 0x0102, flags: 0 };
+        /// let dump = unsafe { new_pointer(&raw const header).debug_dump(8) };
+        ///
+        /// let version = if cfg!(target_endian = "little") { "02 01" } else { "01 02" };
+        /// assert_eq!(dump.to_string(), format!("00000000: 45 4c 46 00 {version} 00 00"));
+        ///
+        /// // every row after the first starts on its own line.
+        /// let bytes = [0xFFu8; 20];
+        /// let dump = unsafe { new_pointer(&raw const bytes).debug_dump(bytes.len()) };
+        /// assert_eq!(
+        ///     dump.to_string(),
+        ///     "00000000: ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff\n\
+        ///      00000010: ff ff ff ff",
+        /// );
+        /// ```
+        #[inline(always)]
+        pub unsafe fn debug_dump<'a>(self, len: usize) -> HexDump<'a> {
+            HexDump(core::slice::from_raw_parts(self.0.cast::<u8>(), len))
+        }
+    }
+
+    /// One step of a [`PointerPath`].
+    #[cfg(feature = "alloc")]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum PathStep {
+        /// Moves the pointer forward by a number of bytes, like `u8+ bytes`.
+        ByteAdd(usize),
+        /// Moves the pointer to an element of an array, like `[index]`.
+        Index {
+            /// The index of the element.
+            index: usize,
+            /// The size of each element in bytes.
+            elem_size: usize,
+        },
+        /// Reads a pointer from the current address and continues from it, like `.*`.
+        Read,
+    }
+
+    /// A list of accesses that is put together at runtime and can then be applied
+    /// to any base pointer.
+    ///
+    /// This is the runtime version of [`element_ptr!`](crate::element_ptr!), for paths
+    /// that are only known at runtime, such as offsets that come from a config file.
+    /// Since the types aren't known, every pointer is just a `*const u8`.
+    ///
+    /// # Examples
+    /// ```
+    /// use element_ptr::helper::{PathStep, PointerPath};
+    /// use std::mem::{offset_of, size_of};
+    ///
+    /// struct Stats {
+    ///     level: u32,
+    ///     health: [u32; 4],
+    /// }
+    ///
+    /// struct Player {
+    ///     id: u64,
+    ///     stats: *const Stats,
+    /// }
+    ///
+    /// let stats = Stats { level: 12, health: [100, 80, 60, 40] };
+    /// let player = Player { id: 7, stats: &stats };
+    ///
+    /// // the same as `element_ptr!(player => .stats.* .health[2])`.
+    /// let path = PointerPath::new()
+    ///     .field_offset(offset_of!(Player, stats))
+    ///     .read()
+    ///     .field_offset(offset_of!(Stats, health))
+    ///     .index(2, size_of::<u32>());
+    ///
+    /// let health = unsafe { path.apply((&player as *const Player).cast()) };
+    /// assert_eq!(unsafe { health.cast::<u32>().read() }, 60);
+    ///
+    /// // paths can also be built from steps directly.
+    /// let level: PointerPath = [
+    ///     PathStep::ByteAdd(offset_of!(Player, stats)),
+    ///     PathStep::Read,
+    ///     PathStep::ByteAdd(offset_of!(Stats, level)),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    /// let level = unsafe { level.apply((&player as *const Player).cast()) };
+    /// assert_eq!(unsafe { level.cast::<u32>().read() }, 12);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct PointerPath {
+        steps: alloc::vec::Vec<PathStep>,
+    }
+
+    #[cfg(feature = "alloc")]
+    impl PointerPath {
+        /// Creates a path without any steps, which leaves the base pointer where it is.
+        #[inline(always)]
+        pub const fn new() -> Self {
+            Self {
+                steps: alloc::vec::Vec::new(),
+            }
+        }
+        /// Adds a step to the end of this path.
+        #[inline(always)]
+        pub fn push(mut self, step: PathStep) -> Self {
+            self.steps.push(step);
+            self
+        }
+        /// Moves to a field that is `offset` bytes into the current value.
+        #[inline(always)]
+        pub fn field_offset(self, offset: usize) -> Self {
+            self.push(PathStep::ByteAdd(offset))
+        }
+        /// Moves to the element `index` of an array with elements that are `elem_size` bytes.
+        #[inline(always)]
+        pub fn index(self, index: usize, elem_size: usize) -> Self {
+            self.push(PathStep::Index { index, elem_size })
+        }
+        /// Moves forward by `bytes` bytes.
+        #[inline(always)]
+        pub fn byte_add(self, bytes: usize) -> Self {
+            self.push(PathStep::ByteAdd(bytes))
+        }
+        /// Reads a pointer from the current address and continues from it.
+        #[inline(always)]
+        pub fn read(self) -> Self {
+            self.push(PathStep::Read)
+        }
+        /// Returns the steps of this path in order.
+        #[inline(always)]
+        pub fn steps(&self) -> &[PathStep] {
+            &self.steps
+        }
+        /// Applies each step of this path to `base`, returning the final pointer.
+        ///
+        /// # Safety
+        /// * Every step has the same requirements as the access in
+        ///   [`element_ptr!`](crate::element_ptr!) that it mirrors. Each offset must stay inside
+        ///   of the allocated object, and each [`Read`](PathStep::Read) must be from an aligned
+        ///   and initialized pointer.
+        ///
+        /// # Panics
+        /// Panics if the byte offset of an [`Index`](PathStep::Index) step, `index * elem_size`,
+        /// overflows a `usize`.
+        ///
+        /// ```should_panic
+        /// # use element_ptr::helper::PointerPath;
+        /// let path = PointerPath::new().index(usize::MAX, 2);
+        /// let data = [0u8; 4];
+        /// unsafe { path.apply(data.as_ptr()) };
+        /// ```
+        pub unsafe fn apply(&self, base: *const u8) -> *const u8 {
+            let mut ptr = new_pointer(base);
+            for step in &self.steps {
+                ptr = match *step {
+                    PathStep::ByteAdd(bytes) => ptr.byte_add(bytes),
+                    PathStep::Index { index, elem_size } => match index.checked_mul(elem_size) {
+                        Some(bytes) => ptr.byte_add(bytes),
+                        None => panic!("index byte offset does not fit in a `usize`"),
+                    },
+                    PathStep::Read => new_pointer(ptr.cast::<*const u8>().read()),
+                };
+            }
+            ptr.into_inner()
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl FromIterator<PathStep> for PointerPath {
+        fn from_iter<I: IntoIterator<Item = PathStep>>(iter: I) -> Self {
+            Self {
+                steps: iter.into_iter().collect(),
+            }
+        }
+    }
+
+    /// A pointer to a pinned value.
+    ///
+    /// This can only be projected into structurally pinned elements, and does
+    /// not have any methods that move the value out from behind it.
+    ///
+    /// # Examples
+    /// ```
+    /// use element_ptr::{element_ptr, helper::PinnedPointer};
+    /// use std::{marker::PhantomPinned, pin::Pin};
+    ///
+    /// struct Task {
+    ///     state: u32,
+    ///     _pinned: PhantomPinned,
+    /// }
+    ///
+    /// let mut task = Box::pin(Task { state: 0, _pinned: PhantomPinned });
+    /// let pinned = PinnedPointer::from(task.as_mut());
+    ///
+    /// // `state` is treated as structurally pinned.
+    /// let state = unsafe { pinned.project_pinned(|ptr| element_ptr!(ptr => .state)) };
+    /// let state: Pin<&mut u32> = unsafe { state.into_pin_mut() };
+    /// *state.get_mut() = 3;
+    ///
+    /// let task: Pin<&Task> = unsafe { pinned.into_pin_ref() };
+    /// assert_eq!(task.state, 3);
+    /// ```
+    #[repr(transparent)]
+    pub struct PinnedPointer<M: Mutability, T: ?Sized>(Pointer<M, T>);
+
+    impl<M: Mutability, T: ?Sized> Clone for PinnedPointer<M, T> {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+    impl<M: Mutability, T: ?Sized> Copy for PinnedPointer<M, T> {}
+
+    impl<M: Mutability, T: ?Sized> Pointer<M, T> {
+        /// Marks the value behind this pointer as pinned.
+        ///
+        /// # Safety
+        /// * The value must follow the [pinning] rules, meaning it must not be moved
+        ///   or invalidated until it is dropped.
+        ///
+        /// [pinning]: core::pin
+        #[inline(always)]
+        pub const unsafe fn pin(self) -> PinnedPointer<M, T> {
+            PinnedPointer(self)
+        }
+    }
+
+    impl<M: Mutability, T: ?Sized> PinnedPointer<M, T> {
+        /// Projects this pointer to an element of the pinned value, keeping it pinned.
+        ///
+        /// This is meant to be used with [`element_ptr!`](crate::element_ptr!), such as
+        /// `pinned.project_pinned(|ptr| element_ptr!(ptr => .field))`.
+        ///
+        /// # Safety
+        /// * The pointer returned by `f` must point to a structurally pinned
+        ///   element of the value. See [pinning projections][proj] for more info.
+        ///
+        /// [proj]: core::pin#projections-and-structural-pinning
+        #[inline(always)]
+        pub unsafe fn project_pinned<U: ?Sized>(
+            self,
+            f: impl FnOnce(M::Raw<T>) -> M::Raw<U>,
+        ) -> PinnedPointer<M, U> {
+            PinnedPointer(new_pointer(f(self.0.into_inner())))
+        }
+        /// Returns the inner pointer type.
+        ///
+        /// The value behind the returned pointer is still pinned.
+        #[inline(always)]
+        pub const fn into_inner(self) -> M::Raw<T> {
+            self.0.into_inner()
+        }
+        /// Returns the pointer without the pinning marker.
+        ///
+        /// # Safety
+        /// * The value must still not be moved, because it is still pinned.
+        #[inline(always)]
+        pub const unsafe fn into_unpinned(self) -> Pointer<M, T> {
+            self.0
+        }
+        /// Turns this pointer back into a pinned reference.
+        ///
+        /// # Safety
+        /// * The pointer must be valid to turn into a `&'a T`.
+        #[inline(always)]
+        pub unsafe fn into_pin_ref<'a>(self) -> Pin<&'a T> {
+            Pin::new_unchecked(&*self.0 .0)
+        }
+    }
+
+    impl<M: CanWrite, T: ?Sized> PinnedPointer<M, T> {
+        /// Turns this pointer back into a pinned mutable reference.
+        ///
+        /// # Safety
+        /// * The pointer must be valid to turn into a `&'a mut T`.
+        #[inline(always)]
+        pub unsafe fn into_pin_mut<'a>(self) -> Pin<&'a mut T> {
+            Pin::new_unchecked(&mut *self.0.into_mut())
+        }
+    }
+
+    impl<'a, T: ?Sized> From<Pin<&'a T>> for PinnedPointer<Const, T> {
+        #[inline(always)]
+        fn from(value: Pin<&'a T>) -> Self {
+            // Safety
+            // `Pin` already promises that the value will not be moved.
+            unsafe { new_base_pointer(Pin::get_ref(value)).pin() }
+        }
+    }
+
+    impl<'a, T: ?Sized> From<Pin<&'a mut T>> for PinnedPointer<Mut, T> {
+        #[inline(always)]
+        fn from(value: Pin<&'a mut T>) -> Self {
+            // Safety
+            // `Pin` already promises that the value will not be moved,
+            // and the reference is never used to move it.
+            unsafe { new_base_pointer(Pin::get_unchecked_mut(value)).pin() }
+        }
+    }
+
+    /// Unwraps the base of a `pin` projection, which is a [`PinnedPointer`],
+    /// a `Pin<&T>`, or a `Pin<&mut T>`.
+    #[doc(hidden)]
+    #[inline(always)]
+    pub fn pinned_base<M: Mutability, T: ?Sized>(ptr: impl Into<PinnedPointer<M, T>>) -> M::Raw<T> {
+        ptr.into().into_inner()
+    }
+
+    /// A pointer that may be sent to another thread.
+    ///
+    /// Raw pointers are not [`Send`], so neither is [`Pointer`]. This wraps one up after the
+    /// caller has promised that using it from another thread is fine, which keeps that promise
+    /// in one place instead of at every use.
+    #[repr(transparent)]
+    pub struct SendPointer<M: Mutability, T: ?Sized>(Pointer<M, T>);
+
+    impl<M: Mutability, T: ?Sized> Clone for SendPointer<M, T> {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+    impl<M: Mutability, T: ?Sized> Copy for SendPointer<M, T> {}
+
+    // Safety
+    // The caller of `Pointer::assert_send` promised that this is fine.
+    unsafe impl<M: Mutability, T: ?Sized> Send for SendPointer<M, T> {}
+
+    impl<M: Mutability, T: ?Sized> Pointer<M, T> {
+        /// Marks this pointer as safe to send to another thread.
+        ///
+        /// # Safety
+        /// * The pointee must be allowed to be accessed from the thread that the pointer
+        ///   is sent to, in every way that the pointer is used there. For example, writing
+        ///   through it requires that no other thread accesses the same memory at the same time,
+        ///   and reading a `T` on another thread generally requires `T: Send`.
+        /// * The pointee must stay valid for as long as it is used on the other thread,
+        ///   which usually means joining the thread before freeing it.
+        ///
+        /// # Examples
+        /// ```
+        /// use element_ptr::element_ptr;
+        /// use std::{mem::MaybeUninit, thread};
+        ///
+        /// struct Frame {
+        ///     header: u32,
+        ///     pixels: [u8; 64],
+        /// }
+        ///
+        /// let mut frame = MaybeUninit::<Frame>::uninit();
+        /// // both threads use pointers from this one, so neither invalidates the other.
+        /// let base = frame.as_mut_ptr();
+        ///
+        /// // Safety: only the worker touches `pixels` until it is joined.
+        /// let pixels = unsafe { element_ptr!(base => .pixels ~).assert_send() };
+        ///
+        /// let worker = thread::spawn(move || {
+        ///     let pixels = pixels.into_pointer();
+        ///     unsafe { pixels.write([255; 64]) };
+        /// });
+        ///
+        /// unsafe { element_ptr!(base => .header <- 1) };
+        /// worker.join().unwrap();
+        ///
+        /// let frame = unsafe { frame.assume_init() };
+        /// assert_eq!((frame.header, frame.pixels), (1, [255; 64]));
+        /// ```
+        #[inline(always)]
+        pub const unsafe fn assert_send(self) -> SendPointer<M, T> {
+            SendPointer(self)
+        }
+    }
+
+    impl<M: Mutability, T: ?Sized> SendPointer<M, T> {
+        /// Returns the pointer, so that it can be used again.
+        ///
+        /// Note that closures capture the fields they use, so the pointer should be taken out
+        /// with this method inside of the closure for it to capture the whole `SendPointer`.
+        #[inline(always)]
+        pub const fn into_pointer(self) -> Pointer<M, T> {
+            self.0
+        }
+        /// Returns the inner pointer type.
+        #[inline(always)]
+        pub const fn into_inner(self) -> M::Raw<T> {
+            self.0.into_inner()
+        }
+    }
+
+    /// Transmutes from `F` to `T`. All of the normal safety requirements
+    /// for transmutations hold here.
+    ///
+    /// This is just `transmute_copy` except by value.
+    #[doc(hidden)]
     pub const unsafe fn transmute_unchecked<F, T>(from: F) -> T {
         #[repr(C)]
         union Transmute<F, T> {
@@ -386,8 +5417,8 @@ pub mod helper {
     /// # Safety
     /// * `E` must be the element of the sequence.
     /// * `Self` must be able to be transmuted to a pointer type.
-    ///     Specifically, a pointer must reside at offset 0 of `Self`,
-    ///     and it must point to a consecutive sequence of `E`s.
+    ///   Specifically, a pointer must reside at offset 0 of `Self`,
+    ///   and it must point to a consecutive sequence of `E`s.
     pub unsafe trait CanIndex {
         type E;
     }
@@ -404,5 +5435,5 @@ pub mod helper {
     /// different "needs an unsafe block" notification.
     #[doc(hidden)]
     #[inline(always)]
-    pub unsafe fn element_ptr_unsafe() {}
+    pub const unsafe fn element_ptr_unsafe() {}
 }